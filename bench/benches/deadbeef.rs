@@ -1,4 +1,8 @@
-use deadbeef_core::{address, config, hex, Configuration, Safe};
+use deadbeef_core::{
+    address, config, hex, Attempts, Configuration, Create2Hasher, Nibbles, Nonces, Pattern, Safe,
+    SaltHasher,
+};
+use sha3::{Digest as _, Keccak256};
 
 fn main() {
     divan::main();
@@ -25,6 +29,7 @@ fn safe() -> Safe {
             )
             .to_vec(),
             singleton: address!(nz "5afe5afe5afe5afe5afe5afe5afe5afe5afe5afe"),
+            chain_specific: None,
         },
         account: config::Account {
             owners: vec![address!(nz "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")],
@@ -42,11 +47,141 @@ fn safe() -> Safe {
 #[divan::bench]
 fn check(bencher: divan::Bencher) {
     let mut safe = safe();
-    let prefix = hex!("deadbeef");
+    let pattern = Pattern::prefix(Nibbles::from_bytes(&hex!("deadbeef"))).unwrap();
 
     bencher.bench_local(move || {
-        deadbeef_core::search_iter(&mut safe, &divan::black_box(prefix), |n| {
+        deadbeef_core::search_iter(&mut safe, divan::black_box(&pattern), |n| {
             n.copy_from_slice(&divan::black_box([0xee; 32]));
         });
     });
 }
+
+#[divan::bench]
+fn check_batch(bencher: divan::Bencher) {
+    let mut safe = safe();
+    let pattern = Pattern::prefix(Nibbles::from_bytes(&hex!("deadbeef"))).unwrap();
+    let nonces = std::array::from_fn(|i| [i as u8; 32]);
+
+    bencher
+        .counter(divan::counter::ItemsCount::new(nonces.len()))
+        .bench_local(move || {
+            deadbeef_core::search_batch(
+                &mut safe,
+                divan::black_box(&pattern),
+                divan::black_box(&nonces),
+            )
+        });
+}
+
+#[divan::bench]
+fn search_range(bencher: divan::Bencher) {
+    const COUNT: u128 = 0x1000;
+
+    let mut safe = safe();
+    let pattern = Pattern::prefix(Nibbles::from_bytes(&hex!("deadbeefdeadbeef"))).unwrap();
+    let attempts = Attempts::new();
+
+    bencher
+        .counter(divan::counter::ItemsCount::new(COUNT as usize))
+        .bench_local(move || {
+            let mut nonces = Nonces::range(42, 0, COUNT);
+            deadbeef_core::search_range(
+                &mut safe,
+                divan::black_box(&pattern),
+                &mut nonces,
+                &attempts,
+            )
+        });
+}
+
+#[divan::bench_group]
+mod keccak {
+    use super::*;
+
+    const SALT_NONCE: [u8; 32] = [0xee; 32];
+
+    #[divan::bench]
+    fn salt_generic(bencher: divan::Bencher) {
+        let initializer_hash = [0x5a; 32];
+        bencher.bench_local(move || {
+            let mut hasher = Keccak256::new();
+            hasher.update(initializer_hash);
+            hasher.update(divan::black_box(SALT_NONCE));
+            <[u8; 32]>::from(hasher.finalize())
+        });
+    }
+
+    #[divan::bench]
+    fn salt_specialised(bencher: divan::Bencher) {
+        let hasher = SaltHasher::new(&[0x5a; 32]);
+        bencher.bench_local(move || hasher.hash(&divan::black_box(SALT_NONCE)));
+    }
+
+    #[divan::bench]
+    fn create2_generic(bencher: divan::Bencher) {
+        let mut input = [0xff; 85];
+        bencher.bench_local(move || {
+            input[21..53].copy_from_slice(&divan::black_box(SALT_NONCE));
+            <[u8; 32]>::from(Keccak256::digest(input))
+        });
+    }
+
+    #[divan::bench]
+    fn create2_specialised(bencher: divan::Bencher) {
+        let mut hasher = Create2Hasher::new(&[0xff; 85]);
+        bencher.bench_local(move || {
+            hasher.set_salt(&divan::black_box(SALT_NONCE));
+            hasher.hash()
+        });
+    }
+}
+
+#[divan::bench_group]
+mod keccak_backend {
+    use deadbeef_core::keccak_backend::*;
+
+    const INPUT: [u8; 85] = [0xff; 85];
+
+    fn v256<K: Keccak>(bencher: divan::Bencher) {
+        bencher.bench_local(|| K::v256_chunked(&[&divan::black_box(INPUT)]));
+    }
+
+    fn f1600<K: Keccak>(bencher: divan::Bencher) {
+        let mut state = [0; 25];
+        bencher.bench_local(move || K::f1600(divan::black_box(&mut state)));
+    }
+
+    #[cfg(feature = "keccak-asm")]
+    #[divan::bench]
+    fn keccak_asm_v256(bencher: divan::Bencher) {
+        v256::<KeccakAsm>(bencher);
+    }
+
+    #[cfg(feature = "keccak-asm")]
+    #[divan::bench]
+    fn keccak_asm_f1600(bencher: divan::Bencher) {
+        f1600::<KeccakAsm>(bencher);
+    }
+
+    #[divan::bench]
+    fn sha3_v256(bencher: divan::Bencher) {
+        v256::<Sha3>(bencher);
+    }
+
+    #[divan::bench]
+    fn sha3_f1600(bencher: divan::Bencher) {
+        f1600::<Sha3>(bencher);
+    }
+
+    #[cfg(feature = "tiny-keccak")]
+    #[divan::bench]
+    fn tiny_keccak_v256(bencher: divan::Bencher) {
+        v256::<TinyKeccak>(bencher);
+    }
+
+    #[cfg(feature = "tiny-keccak")]
+    #[divan::bench]
+    fn tiny_keccak_f1600(bencher: divan::Bencher) {
+        f1600::<TinyKeccak>(bencher);
+    }
+}
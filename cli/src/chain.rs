@@ -1,6 +1,6 @@
 //! Module for chain-specific data.
 
-use crate::deployment::{self, Deployment};
+use crate::deployment::{self, Deployment, Version};
 use deadbeef_core::Address;
 use std::{
     fmt::{self, Display, Formatter},
@@ -18,91 +18,130 @@ impl Chain {
         Self(1)
     }
 
+    /// Returns the chain ID.
+    pub fn id(&self) -> u128 {
+        self.0
+    }
+
+    /// Returns the reason why Safes on this chain cannot be mined, even with
+    /// contract overrides, or [`None`] if they can.
+    pub fn unsupported(&self) -> Option<&'static str> {
+        match self.0 {
+            // zkSync Era derives `CREATE2` addresses from EraVM bytecode
+            // hashes, so overrides would predict the wrong addresses.
+            324 => Some("zkSync Era is currently not supported"),
+            _ => None,
+        }
+    }
+
     /// Returns the [`Details`] for this chain, or [`None`] if the chain is
     /// not officially supported.
     pub fn details(&self) -> Option<Details> {
         match self.0 {
             1 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[
+                    (Version::V1_4_1, &deployment::v1_4_1::CANONICAL),
+                    (Version::V1_3_0, &deployment::v1_3_0::CANONICAL),
+                ],
                 explorer: Explorer::etherscan("https://etherscan.io"),
                 singleton: Singleton::Safe,
             }),
             10 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[
+                    (Version::V1_4_1, &deployment::v1_4_1::CANONICAL),
+                    (Version::V1_3_0, &deployment::v1_3_0::CANONICAL),
+                ],
                 explorer: Explorer::etherscan("https://optimistic.etherscan.io"),
                 singleton: Singleton::SafeL2,
             }),
             56 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[
+                    (Version::V1_4_1, &deployment::v1_4_1::CANONICAL),
+                    (Version::V1_3_0, &deployment::v1_3_0::CANONICAL),
+                ],
                 explorer: Explorer::etherscan("https://bscscan.com"),
                 singleton: Singleton::SafeL2,
             }),
             100 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[
+                    (Version::V1_4_1, &deployment::v1_4_1::CANONICAL),
+                    (Version::V1_3_0, &deployment::v1_3_0::CANONICAL),
+                ],
                 explorer: Explorer::etherscan("https://gnosisscan.io"),
                 singleton: Singleton::SafeL2,
             }),
             130 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[(Version::V1_4_1, &deployment::v1_4_1::CANONICAL)],
                 explorer: Explorer::etherscan("https://uniscan.xyz"),
                 singleton: Singleton::SafeL2,
             }),
             137 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[
+                    (Version::V1_4_1, &deployment::v1_4_1::CANONICAL),
+                    (Version::V1_3_0, &deployment::v1_3_0::CANONICAL),
+                ],
                 explorer: Explorer::etherscan("https://polygonscan.com"),
                 singleton: Singleton::SafeL2,
             }),
             146 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[(Version::V1_4_1, &deployment::v1_4_1::CANONICAL)],
                 explorer: Explorer::etherscan("https://sonicscan.org"),
                 singleton: Singleton::SafeL2,
             }),
             196 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[(Version::V1_4_1, &deployment::v1_4_1::CANONICAL)],
                 explorer: Explorer {
                     url: "https://www.oklink.com/xlayer",
                     selector: "/contract#category=write&id=2",
                 },
                 singleton: Singleton::SafeL2,
             }),
-            324 => todo!("zkSync Era is currently not supported."),
             480 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[(Version::V1_4_1, &deployment::v1_4_1::CANONICAL)],
                 explorer: Explorer::blockscout("https://worldchain-mainnet.explorer.alchemy.com"),
                 singleton: Singleton::SafeL2,
             }),
             1101 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[(Version::V1_4_1, &deployment::v1_4_1::CANONICAL)],
                 explorer: Explorer::etherscan("https://zkevm.polygonscan.com"),
                 singleton: Singleton::SafeL2,
             }),
             5000 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[(Version::V1_4_1, &deployment::v1_4_1::CANONICAL)],
                 explorer: Explorer::etherscan("https://mantlescan.xyz"),
                 singleton: Singleton::SafeL2,
             }),
             8453 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[
+                    (Version::V1_4_1, &deployment::v1_4_1::CANONICAL),
+                    (Version::V1_3_0, &deployment::v1_3_0::CANONICAL),
+                ],
                 explorer: Explorer::etherscan("https://basescan.org"),
                 singleton: Singleton::SafeL2,
             }),
             10200 => Some(Details {
-                deployment: &deployment::v1_3_0::CANONICAL,
+                deployments: &[(Version::V1_3_0, &deployment::v1_3_0::CANONICAL)],
                 explorer: Explorer::blockscout("https://gnosis-chiado.blockscout.com"),
                 singleton: Singleton::SafeL2,
             }),
             42161 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[
+                    (Version::V1_4_1, &deployment::v1_4_1::CANONICAL),
+                    (Version::V1_3_0, &deployment::v1_3_0::CANONICAL),
+                ],
                 explorer: Explorer::etherscan("https://arbiscan.io"),
                 singleton: Singleton::SafeL2,
             }),
             42220 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[(Version::V1_4_1, &deployment::v1_4_1::CANONICAL)],
                 explorer: Explorer::blockscout("https://explorer.celo.org/mainnet"),
                 singleton: Singleton::SafeL2,
             }),
             43114 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[
+                    (Version::V1_4_1, &deployment::v1_4_1::CANONICAL),
+                    (Version::V1_3_0, &deployment::v1_3_0::CANONICAL),
+                ],
                 explorer: Explorer {
                     url: "https://snowtrace.io",
                     selector: "/contract/43114/writeContract?chainid=43114#F3",
@@ -110,42 +149,45 @@ impl Chain {
                 singleton: Singleton::SafeL2,
             }),
             57073 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[(Version::V1_4_1, &deployment::v1_4_1::CANONICAL)],
                 explorer: Explorer::blockscout("https://explorer.inkonchain.com"),
                 singleton: Singleton::SafeL2,
             }),
             59144 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[(Version::V1_4_1, &deployment::v1_4_1::CANONICAL)],
                 explorer: Explorer::etherscan("https://lineascan.build"),
                 singleton: Singleton::SafeL2,
             }),
             80094 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[(Version::V1_4_1, &deployment::v1_4_1::CANONICAL)],
                 explorer: Explorer::etherscan("https://berascan.com"),
                 singleton: Singleton::SafeL2,
             }),
             81457 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[(Version::V1_4_1, &deployment::v1_4_1::CANONICAL)],
                 explorer: Explorer::etherscan("https://blastscan.io"),
                 singleton: Singleton::SafeL2,
             }),
             84532 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[(Version::V1_4_1, &deployment::v1_4_1::CANONICAL)],
                 explorer: Explorer::etherscan("https://sepolia.basescan.org"),
                 singleton: Singleton::SafeL2,
             }),
             534352 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[(Version::V1_4_1, &deployment::v1_4_1::CANONICAL)],
                 explorer: Explorer::etherscan("https://scrollscan.com"),
                 singleton: Singleton::SafeL2,
             }),
             11155111 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[
+                    (Version::V1_4_1, &deployment::v1_4_1::CANONICAL),
+                    (Version::V1_3_0, &deployment::v1_3_0::CANONICAL),
+                ],
                 explorer: Explorer::etherscan("https://sepolia.etherscan.io"),
                 singleton: Singleton::SafeL2,
             }),
             1313161554 => Some(Details {
-                deployment: &deployment::v1_4_1::CANONICAL,
+                deployments: &[(Version::V1_4_1, &deployment::v1_4_1::CANONICAL)],
                 explorer: Explorer::blockscout("https://aurorascan.dev"),
                 singleton: Singleton::SafeL2,
             }),
@@ -211,15 +253,35 @@ impl FromStr for Chain {
 
 /// The chain details.
 pub struct Details {
-    deployment: &'static Deployment,
+    deployments: &'static [(Version, &'static Deployment)],
     explorer: Explorer,
     singleton: Singleton,
 }
 
 impl Details {
-    /// Returns the deployment information for the chain.
+    /// Returns the deployment information for the latest Safe version on the
+    /// chain.
     pub fn deployment(&self) -> &Deployment {
-        self.deployment
+        self.deployments[0].1
+    }
+
+    /// Returns the latest Safe version deployed on the chain.
+    pub fn latest_version(&self) -> Version {
+        self.deployments[0].0
+    }
+
+    /// Returns the deployment information for the specified Safe version, or
+    /// [`None`] if the version is not deployed on the chain.
+    pub fn version(&self, version: Version) -> Option<&Deployment> {
+        self.deployments
+            .iter()
+            .find(|(v, _)| *v == version)
+            .map(|(_, deployment)| *deployment)
+    }
+
+    /// Returns the Safe versions deployed on the chain.
+    pub fn versions(&self) -> impl Iterator<Item = Version> + '_ {
+        self.deployments.iter().map(|(version, _)| *version)
     }
 
     /// Returns the explorer URL to the `createProxyWithNonce` function.
@@ -284,8 +346,24 @@ mod tests {
             let details = Chain(chain).details().unwrap();
             let url = details
                 .explorer
-                .create_proxy_with_nonce_url(details.deployment.safe_proxy_factory.get());
+                .create_proxy_with_nonce_url(details.deployment().safe_proxy_factory.get());
             println!("{}: {}", chain, url);
         }
     }
+
+    #[test]
+    fn deployment_versions() {
+        let details = Chain(10).details().unwrap();
+        assert_eq!(
+            details.versions().collect::<Vec<_>>(),
+            [Version::V1_4_1, Version::V1_3_0],
+        );
+        assert_eq!(
+            details.version(Version::V1_3_0).unwrap().safe_proxy_factory,
+            deployment::v1_3_0::CANONICAL.safe_proxy_factory,
+        );
+
+        let details = Chain(10200).details().unwrap();
+        assert!(details.version(Version::V1_4_1).is_none());
+    }
 }
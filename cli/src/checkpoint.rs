@@ -0,0 +1,206 @@
+//! Module for checkpointing long running searches.
+
+use deadbeef_core::Nonces;
+use std::{
+    fmt::{self, Display, Formatter},
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+    str::FromStr,
+};
+
+/// A search checkpoint, recording the salt nonces that each worker has
+/// covered so far.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    /// The digest of the Safe configuration being searched.
+    pub config: [u8; 32],
+    /// The patterns being searched for.
+    pub patterns: Vec<String>,
+    /// Whether or not the patterns are matched with checksum case.
+    pub checksum: bool,
+    /// The salt nonce seed.
+    pub seed: u128,
+    /// The counter ranges for each worker.
+    pub workers: Vec<Worker>,
+}
+
+/// The counter range for a single search worker.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Worker {
+    /// The first counter of the worker's range.
+    pub start: u128,
+    /// The next counter to search, all counters before it have been covered.
+    pub next: u128,
+    /// The exclusive end counter of the worker's range.
+    pub end: u128,
+}
+
+impl Checkpoint {
+    /// Reads a checkpoint from the specified file.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
+    }
+
+    /// Writes the checkpoint to the specified file.
+    ///
+    /// The checkpoint is first written to a temporary file and then renamed,
+    /// so that a crash while saving does not corrupt an existing checkpoint.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let temp = path.with_extension("tmp");
+        fs::write(&temp, self.to_string())?;
+        fs::rename(&temp, path)
+    }
+
+    /// Updates the worker progress from the remaining salt nonce ranges.
+    pub fn update(&mut self, remaining: &[Nonces]) {
+        for (worker, nonces) in self.workers.iter_mut().zip(remaining) {
+            worker.next = nonces.start();
+        }
+    }
+
+    /// Returns the remaining salt nonce ranges for each worker.
+    pub fn remaining(&self) -> Vec<Nonces> {
+        self.workers
+            .iter()
+            .map(|worker| Nonces::range(self.seed, worker.next, worker.end))
+            .collect()
+    }
+}
+
+impl Display for Checkpoint {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "config:   0x{}", hex::encode(self.config))?;
+        for pattern in &self.patterns {
+            writeln!(f, "pattern:  {pattern}")?;
+        }
+        writeln!(f, "checksum: {}", self.checksum)?;
+        writeln!(f, "seed:     {:#x}", self.seed)?;
+        for worker in &self.workers {
+            writeln!(
+                f,
+                "worker:   {} {} {}",
+                worker.start, worker.next, worker.end
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Checkpoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mut config, mut checksum, mut seed) = (None, None, None);
+        let (mut patterns, mut workers) = (Vec::new(), Vec::new());
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| format!("invalid line '{line}'"))?;
+            let value = value.trim();
+            match key {
+                "config" => {
+                    let mut digest = [0; 32];
+                    hex::decode_to_slice(value.trim_start_matches("0x"), &mut digest)
+                        .map_err(|err| format!("invalid config digest: {err}"))?;
+                    config = Some(digest);
+                }
+                "pattern" => patterns.push(value.to_owned()),
+                "checksum" => {
+                    checksum = Some(
+                        value
+                            .parse()
+                            .map_err(|err| format!("invalid checksum flag: {err}"))?,
+                    );
+                }
+                "seed" => {
+                    seed = Some(
+                        u128::from_str_radix(value.trim_start_matches("0x"), 16)
+                            .map_err(|err| format!("invalid seed: {err}"))?,
+                    );
+                }
+                "worker" => {
+                    let counters = value
+                        .split_whitespace()
+                        .map(str::parse)
+                        .collect::<Result<Vec<u128>, _>>()
+                        .map_err(|err| format!("invalid worker range: {err}"))?;
+                    let [start, next, end] = counters[..] else {
+                        return Err(format!("invalid worker range '{value}'"));
+                    };
+                    if !(start <= next && next <= end) {
+                        return Err(format!("invalid worker range '{value}'"));
+                    }
+                    workers.push(Worker { start, next, end });
+                }
+                _ => return Err(format!("unknown key '{key}'")),
+            }
+        }
+
+        if workers.is_empty() {
+            return Err("missing worker ranges".to_owned());
+        }
+        Ok(Self {
+            config: config.ok_or("missing config digest")?,
+            patterns,
+            checksum: checksum.ok_or("missing checksum flag")?,
+            seed: seed.ok_or("missing seed")?,
+            workers,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let mut checkpoint = Checkpoint {
+            config: [0x42; 32],
+            patterns: vec![
+                "0x5afe????????????????????????????????????".to_owned(),
+                "0xDEADbeef????????????????????????????????".to_owned(),
+            ],
+            checksum: true,
+            seed: 0x5afe,
+            workers: vec![
+                Worker {
+                    start: 0,
+                    next: 0,
+                    end: 100,
+                },
+                Worker {
+                    start: 100,
+                    next: 100,
+                    end: 200,
+                },
+            ],
+        };
+        checkpoint.update(&[
+            Nonces::range(0x5afe, 42, 100),
+            Nonces::range(0x5afe, 200, 200),
+        ]);
+
+        assert_eq!(
+            checkpoint.to_string(),
+            "config:   0x4242424242424242424242424242424242424242424242424242424242424242\n\
+             pattern:  0x5afe????????????????????????????????????\n\
+             pattern:  0xDEADbeef????????????????????????????????\n\
+             checksum: true\n\
+             seed:     0x5afe\n\
+             worker:   0 42 100\n\
+             worker:   100 200 200\n",
+        );
+        assert_eq!(checkpoint.to_string().parse(), Ok(checkpoint.clone()));
+        assert_eq!(
+            checkpoint.remaining(),
+            [
+                Nonces::range(0x5afe, 42, 100),
+                Nonces::range(0x5afe, 200, 200)
+            ],
+        );
+    }
+}
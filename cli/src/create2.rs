@@ -0,0 +1,158 @@
+//! Module for mining `CREATE2` salts for arbitrary factories.
+
+use crate::{Hex, SearchArgs, Word};
+use clap::ArgGroup;
+use deadbeef_core::{deployer::Deployer, Address, Create2, NonZeroAddress, Nonces, Pattern};
+use std::{process, str::FromStr};
+
+/// Arguments for the `create2` command.
+#[derive(Clone, clap::Args)]
+#[command(
+    group(ArgGroup::new("pattern").required(true).multiple(true)),
+    group(ArgGroup::new("init").required(true)),
+)]
+pub struct Args {
+    #[command(flatten)]
+    search: SearchArgs,
+
+    /// The factory contract that deploys with `CREATE2`.
+    ///
+    /// Either an address, or one of the well-known deployers 'arachnid'
+    /// (Arachnid's deterministic deployment proxy), 'safe-singleton-factory'
+    /// or 'immutable-create2-factory'. For well-known deployers, only salts
+    /// that the deployer accepts are searched, and the deployment transaction
+    /// is printed if the init code is specified.
+    #[arg(long)]
+    deployer: DeployerArg,
+
+    /// The address that calls the deployer, for deployers that restrict the
+    /// salt to the caller.
+    ///
+    /// For 'immutable-create2-factory', this makes the first 20 bytes of the
+    /// salt the caller address, so that the deployment cannot be
+    /// front-run. Otherwise, they are zero and anyone can deploy.
+    #[arg(long)]
+    caller: Option<NonZeroAddress>,
+
+    /// The init code of the contract to deploy.
+    #[arg(long, group = "init")]
+    init_code: Option<Hex>,
+
+    /// The Keccak-256 hash of the init code of the contract to deploy.
+    #[arg(long, group = "init")]
+    init_code_hash: Option<Word>,
+
+    /// Quiet mode.
+    ///
+    /// Only output the deployment calldata, or the salt if the deployer or
+    /// init code is not known, without any extra information.
+    #[arg(short, long)]
+    quiet: bool,
+}
+
+/// Helper type for parsing a deployer address or well-known deployer name
+/// from the command line.
+#[derive(Clone, Copy)]
+enum DeployerArg {
+    Preset(Deployer),
+    Address(NonZeroAddress),
+}
+
+impl DeployerArg {
+    const PRESETS: [(&str, Deployer); 3] = [
+        ("arachnid", Deployer::DeterministicDeploymentProxy),
+        ("safe-singleton-factory", Deployer::SafeSingletonFactory),
+        (
+            "immutable-create2-factory",
+            Deployer::ImmutableCreate2Factory,
+        ),
+    ];
+
+    fn address(self) -> Address {
+        match self {
+            Self::Preset(deployer) => deployer.address(),
+            Self::Address(address) => address.get(),
+        }
+    }
+}
+
+impl FromStr for DeployerArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((_, deployer)) = Self::PRESETS.iter().find(|(name, _)| *name == s) {
+            return Ok(Self::Preset(*deployer));
+        }
+
+        // Well-known deployers specified by address still use their salt
+        // rules, as any other salt would fail to deploy.
+        let address = s.parse::<NonZeroAddress>().map_err(|err| err.to_string())?;
+        Ok(Self::PRESETS
+            .iter()
+            .find(|(_, deployer)| deployer.address() == address.get())
+            .map(|(_, deployer)| Self::Preset(*deployer))
+            .unwrap_or(Self::Address(address)))
+    }
+}
+
+/// Mines a `CREATE2` salt for an address matching the pattern.
+pub fn run(args: Args) {
+    let salts = match args.deployer {
+        DeployerArg::Preset(deployer) => deployer.salts(args.caller.map(NonZeroAddress::get)),
+        DeployerArg::Address(_) => None,
+    };
+    if args.caller.is_some() && salts.is_none() {
+        eprintln!("the deployer does not restrict the salt to the caller");
+        process::exit(1);
+    }
+
+    let deployer = args.deployer.address();
+    let create2 = match (&args.init_code, args.init_code_hash) {
+        (Some(Hex(init_code)), _) => Create2::with_init_code(deployer, [0; 32], init_code),
+        (None, Some(Word(init_code_hash))) => Create2::new(deployer, [0; 32], init_code_hash),
+        (None, None) => unreachable!("missing init code"),
+    };
+
+    crate::run(
+        &args.search,
+        create2,
+        salts,
+        args.quiet,
+        |create2, patterns, index, attempts| print(&args, create2, patterns, index, attempts),
+    );
+}
+
+/// Prints the result of a `CREATE2` salt search.
+fn print(args: &Args, create2: &Create2, patterns: &[Pattern], index: usize, attempts: u64) {
+    let calldata = match (args.deployer, &args.init_code) {
+        (DeployerArg::Preset(deployer), Some(Hex(init_code))) => {
+            Some(deployer.calldata(create2.salt(), init_code))
+        }
+        _ => None,
+    };
+
+    if args.quiet {
+        match calldata {
+            Some(calldata) => println!("0x{}", hex::encode(calldata)),
+            None => println!("0x{}", hex::encode(create2.salt())),
+        }
+        return;
+    }
+
+    if patterns.len() > 1 {
+        println!("pattern:     {}", patterns[index]);
+    }
+    println!("address:     {}", create2.creation_address());
+    println!("deployer:    {}", create2.factory());
+    println!("init hash:   0x{}", hex::encode(create2.init_code_hash()));
+    println!("salt:        0x{}", hex::encode(create2.salt()));
+    let (seed, counter) = Nonces::split(create2.salt());
+    println!("seed:        {:#x}", seed);
+    println!("counter:     {}", counter);
+    println!("attempts:    {}", attempts);
+    if let Some(calldata) = calldata {
+        println!("---");
+        println!("to:          {}", create2.factory());
+        println!("calldata:    0x{}", hex::encode(calldata));
+    }
+}
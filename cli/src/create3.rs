@@ -0,0 +1,92 @@
+//! Module for mining `CREATE3` salts for arbitrary factories.
+
+use crate::{SearchArgs, Word};
+use clap::ArgGroup;
+use deadbeef_core::{create3, Create3, NonZeroAddress, Nonces, Pattern};
+use std::str::FromStr;
+
+/// Arguments for the `create3` command.
+#[derive(Clone, clap::Args)]
+#[command(group(ArgGroup::new("pattern").required(true).multiple(true)))]
+pub struct Args {
+    #[command(flatten)]
+    search: SearchArgs,
+
+    /// The factory contract that deploys the proxy with `CREATE2`.
+    ///
+    /// This is the contract that uses the `CREATE3` library. The mined salt is
+    /// the raw salt it deploys the proxy with, so factories that derive the
+    /// proxy salt from the one they are called with are not supported.
+    #[arg(long)]
+    deployer: NonZeroAddress,
+
+    /// The proxy that the factory deploys.
+    ///
+    /// Either the Keccak-256 hash of the proxy init code, or 'solady' for the
+    /// proxy used by the Solady, solmate and 0xSequence `CREATE3` libraries.
+    #[arg(long, default_value = "solady")]
+    proxy: ProxyArg,
+
+    /// Quiet mode.
+    ///
+    /// Only output the salt without any extra information.
+    #[arg(short, long)]
+    quiet: bool,
+}
+
+/// Helper type for parsing a proxy init code hash or well-known proxy name
+/// from the command line.
+#[derive(Clone, Copy)]
+struct ProxyArg([u8; 32]);
+
+impl ProxyArg {
+    const PRESETS: [(&str, [u8; 32]); 1] = [("solady", create3::PROXY_INIT_CODE_HASH)];
+}
+
+impl FromStr for ProxyArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((_, hash)) = Self::PRESETS.iter().find(|(name, _)| *name == s) {
+            return Ok(Self(*hash));
+        }
+        let Word(hash) = s.parse()?;
+        Ok(Self(hash))
+    }
+}
+
+/// Mines a `CREATE3` salt for an address matching the pattern.
+pub fn run(args: Args) {
+    let create3 = Create3::new(args.deployer.get(), [0; 32], args.proxy.0);
+    crate::run(
+        &args.search,
+        create3,
+        None,
+        args.quiet,
+        |create3, patterns, index, attempts| print(&args, create3, patterns, index, attempts),
+    );
+}
+
+/// Prints the result of a `CREATE3` salt search.
+fn print(args: &Args, create3: &Create3, patterns: &[Pattern], index: usize, attempts: u64) {
+    if args.quiet {
+        println!("0x{}", hex::encode(create3.salt()));
+        return;
+    }
+
+    if patterns.len() > 1 {
+        println!("pattern:     {}", patterns[index]);
+    }
+    println!("address:     {}", create3.creation_address());
+    println!("proxy:       {}", create3.proxy_address());
+    println!("deployer:    {}", create3.factory());
+    println!(
+        "proxy hash:  0x{}",
+        hex::encode(create3.proxy_init_code_hash())
+    );
+    println!("salt:        0x{}", hex::encode(create3.salt()));
+    let (seed, counter) = Nonces::split(create3.salt());
+    println!("seed:        {:#x}", seed);
+    println!("counter:     {}", counter);
+    println!("attempts:    {}", attempts);
+}
@@ -0,0 +1,137 @@
+//! Module for mining salts for deployments through the CreateX factory.
+
+use crate::{Hex, SearchArgs, Uint, Word};
+use clap::{ArgGroup, ValueEnum};
+use deadbeef_core::{
+    createx::{self, CreateX, Protection},
+    Create2, NonZeroAddress, Nonces, Pattern,
+};
+use std::process;
+
+/// Arguments for the `createx` command.
+#[derive(Clone, clap::Args)]
+#[command(group(ArgGroup::new("pattern").required(true).multiple(true)))]
+pub struct Args {
+    #[command(flatten)]
+    search: SearchArgs,
+
+    /// The CreateX function to deploy with.
+    #[arg(long, value_enum, default_value_t = Entrypoint::Create2)]
+    entrypoint: Entrypoint,
+
+    /// The address that calls CreateX.
+    ///
+    /// This enables permissioned deploy protection, so that only the caller
+    /// can deploy with the salt and the deployment cannot be front-run.
+    /// Otherwise, anyone can deploy.
+    #[arg(long)]
+    caller: Option<NonZeroAddress>,
+
+    /// The chain ID to deploy on.
+    ///
+    /// This enables cross-chain redeploy protection, so that the salt deploys
+    /// to a different address on other chains.
+    #[arg(long)]
+    chain: Option<Uint>,
+
+    /// The init code of the contract to deploy.
+    ///
+    /// Required for printing the deployment calldata. `deployCreate2`
+    /// addresses require either the init code or its hash.
+    #[arg(long, conflicts_with = "init_code_hash")]
+    init_code: Option<Hex>,
+
+    /// The Keccak-256 hash of the init code of the contract to deploy.
+    #[arg(long)]
+    init_code_hash: Option<Word>,
+
+    /// Quiet mode.
+    ///
+    /// Only output the deployment calldata, or the salt if the init code is
+    /// not known, without any extra information.
+    #[arg(short, long)]
+    quiet: bool,
+}
+
+/// A CreateX function to deploy with.
+#[derive(Clone, Copy, ValueEnum)]
+enum Entrypoint {
+    /// `deployCreate2(bytes32,bytes)`.
+    Create2,
+    /// `deployCreate3(bytes32,bytes)`, for addresses that do not depend on
+    /// the init code.
+    Create3,
+}
+
+/// Mines a CreateX salt for an address matching the pattern.
+pub fn run(args: Args) {
+    let protection = Protection {
+        caller: args.caller.map(NonZeroAddress::get),
+        chain_id: args.chain.map(|Uint(chain_id)| chain_id),
+    };
+    let createx = match args.entrypoint {
+        Entrypoint::Create2 => {
+            let init_code_hash = match (&args.init_code, args.init_code_hash) {
+                (Some(Hex(init_code)), _) => {
+                    Create2::with_init_code(createx::ADDRESS, [0; 32], init_code).init_code_hash()
+                }
+                (None, Some(Word(init_code_hash))) => init_code_hash,
+                (None, None) => {
+                    eprintln!("`deployCreate2` requires the init code or its hash");
+                    process::exit(1);
+                }
+            };
+            CreateX::create2(protection, init_code_hash)
+        }
+        Entrypoint::Create3 => {
+            if args.init_code_hash.is_some() {
+                eprintln!("`deployCreate3` addresses do not depend on the init code hash");
+                process::exit(1);
+            }
+            CreateX::create3(protection)
+        }
+    };
+
+    crate::run(
+        &args.search,
+        createx,
+        Some(protection.salts()),
+        args.quiet,
+        |createx, patterns, index, attempts| print(&args, createx, patterns, index, attempts),
+    );
+}
+
+/// Prints the result of a CreateX salt search.
+fn print(args: &Args, createx: &CreateX, patterns: &[Pattern], index: usize, attempts: u64) {
+    let calldata = args
+        .init_code
+        .as_ref()
+        .map(|Hex(init_code)| createx.calldata(init_code));
+
+    if args.quiet {
+        match calldata {
+            Some(calldata) => println!("0x{}", hex::encode(calldata)),
+            None => println!("0x{}", hex::encode(createx.salt())),
+        }
+        return;
+    }
+
+    if patterns.len() > 1 {
+        println!("pattern:     {}", patterns[index]);
+    }
+    println!("address:     {}", createx.creation_address());
+    if let Some(proxy) = createx.proxy_address() {
+        println!("proxy:       {}", proxy);
+    }
+    println!("salt:        0x{}", hex::encode(createx.salt()));
+    println!("guarded:     0x{}", hex::encode(createx.guarded_salt()));
+    let (seed, counter) = Nonces::split(createx.salt());
+    println!("seed:        {:#x}", seed);
+    println!("counter:     {}", counter);
+    println!("attempts:    {}", attempts);
+    if let Some(calldata) = calldata {
+        println!("---");
+        println!("to:          {}", createx::ADDRESS);
+        println!("calldata:    0x{}", hex::encode(calldata));
+    }
+}
@@ -1,4 +1,35 @@
+use clap::ValueEnum;
 use deadbeef_core::{address, hex, Address, NonZeroAddress};
+use std::fmt::{self, Display, Formatter};
+
+/// A Safe contract deployment version.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Version {
+    /// The v1.4.1 deployment.
+    #[value(name = "1.4.1")]
+    V1_4_1,
+    /// The v1.3.0 deployment.
+    #[value(name = "1.3.0")]
+    V1_3_0,
+}
+
+impl Version {
+    /// Returns whether the version's proxy factory has
+    /// `createChainSpecificProxyWithNonce`.
+    pub fn has_chain_specific_proxies(self) -> bool {
+        match self {
+            Self::V1_4_1 => true,
+            Self::V1_3_0 => false,
+        }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let value = self.to_possible_value().expect("no skipped versions");
+        f.write_str(value.get_name())
+    }
+}
 
 /// Safe contract deployment.
 #[derive(Clone)]
@@ -27,7 +58,7 @@ pub mod v1_4_1 {
     use super::*;
 
     /// The canonical contract deployment.
-    pub static CANONICAL: Deployment = Deployment {
+    pub const CANONICAL: Deployment = Deployment {
         safe_proxy_factory: address!(nz "4e1DCf7AD4e460CfD30791CCC4F9c8a4f820ec67"),
         safe_proxy_init_code: &hex!(
             "608060405234801561001057600080fd5b506040516101e63803806101e68339
@@ -64,6 +95,7 @@ pub mod v1_4_1 {
                 factory: CANONICAL.safe_proxy_factory,
                 init_code: CANONICAL.safe_proxy_init_code.to_vec(),
                 singleton: CANONICAL.safe,
+                chain_specific: None,
             };
             assert_eq!(
                 proxy.init_code_hash(),
@@ -79,6 +111,7 @@ pub mod v1_4_1 {
                     factory: CANONICAL.safe_proxy_factory,
                     init_code: CANONICAL.safe_proxy_init_code.to_vec(),
                     singleton: CANONICAL.safe,
+                    chain_specific: None,
                 },
                 account: config::Account {
                     owners: vec![
@@ -114,27 +147,30 @@ pub mod v1_4_1 {
 pub mod v1_3_0 {
     use super::*;
 
+    /// The `proxyCreationCode`.
+    const PROXY_INIT_CODE: &[u8] = &hex!(
+        "608060405234801561001057600080fd5b506040516101e63803806101e68339
+         818101604052602081101561003357600080fd5b810190808051906020019092
+         9190505050600073ffffffffffffffffffffffffffffffffffffffff168173ff
+         ffffffffffffffffffffffffffffffffffffff1614156100ca576040517f08c3
+         79a0000000000000000000000000000000000000000000000000000000008152
+         6004018080602001828103825260228152602001806101c46022913960400191
+         505060405180910390fd5b806000806101000a81548173ffffffffffffffffff
+         ffffffffffffffffffffff021916908373ffffffffffffffffffffffffffffff
+         ffffffffff1602179055505060ab806101196000396000f3fe608060405273ff
+         ffffffffffffffffffffffffffffffffffffff600054167fa619486e00000000
+         0000000000000000000000000000000000000000000000006000351415605057
+         8060005260206000f35b3660008037600080366000845af43d6000803e600081
+         14156070573d6000fd5b3d6000f3fea2646970667358221220d1429297349653
+         a4918076d650332de1a1068c5f3e07c5c82360c277770b955264736f6c634300
+         07060033496e76616c69642073696e676c65746f6e2061646472657373207072
+         6f7669646564"
+    );
+
     /// The canonical contract deployment.
-    pub static CANONICAL: Deployment = Deployment {
+    pub const CANONICAL: Deployment = Deployment {
         safe_proxy_factory: address!(nz "a6B71E26C5e0845f74c812102Ca7114b6a896AB2"),
-        safe_proxy_init_code: &hex!(
-            "608060405234801561001057600080fd5b506040516101e63803806101e68339
-             818101604052602081101561003357600080fd5b810190808051906020019092
-             9190505050600073ffffffffffffffffffffffffffffffffffffffff168173ff
-             ffffffffffffffffffffffffffffffffffffff1614156100ca576040517f08c3
-             79a0000000000000000000000000000000000000000000000000000000008152
-             6004018080602001828103825260228152602001806101c46022913960400191
-             505060405180910390fd5b806000806101000a81548173ffffffffffffffffff
-             ffffffffffffffffffffff021916908373ffffffffffffffffffffffffffffff
-             ffffffffff1602179055505060ab806101196000396000f3fe608060405273ff
-             ffffffffffffffffffffffffffffffffffffff600054167fa619486e00000000
-             0000000000000000000000000000000000000000000000006000351415605057
-             8060005260206000f35b3660008037600080366000845af43d6000803e600081
-             14156070573d6000fd5b3d6000f3fea2646970667358221220d1429297349653
-             a4918076d650332de1a1068c5f3e07c5c82360c277770b955264736f6c634300
-             07060033496e76616c69642073696e676c65746f6e2061646472657373207072
-             6f7669646564"
-        ),
+        safe_proxy_init_code: PROXY_INIT_CODE,
         safe: address!(nz "d9Db270c1B5E3Bd161E8c8503c55cEABeE709552"),
         safe_l2: address!(nz "3E5c63644E683549055b9Be8653de26E0B4CD36E"),
         safe_to_l2_setup: Address::zero(),
@@ -152,6 +188,7 @@ pub mod v1_3_0 {
                 factory: CANONICAL.safe_proxy_factory,
                 init_code: CANONICAL.safe_proxy_init_code.to_vec(),
                 singleton: CANONICAL.safe,
+                chain_specific: None,
             };
             assert_eq!(
                 proxy.init_code_hash(),
@@ -167,6 +204,7 @@ pub mod v1_3_0 {
                     factory: CANONICAL.safe_proxy_factory,
                     init_code: CANONICAL.safe_proxy_init_code.to_vec(),
                     singleton: CANONICAL.safe,
+                    chain_specific: None,
                 },
                 account: config::Account {
                     owners: vec![
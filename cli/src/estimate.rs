@@ -0,0 +1,125 @@
+//! Module for estimating search times on the current machine.
+
+use crate::{
+    chain::Chain,
+    status::{Si, Time},
+    PatternArgs, Timeout,
+};
+use clap::ArgGroup;
+use deadbeef_core::{
+    address, config, Address, CancellationToken, Checksum, Configuration, Estimate, Matcher,
+    Nonces, Patterns, Safe, Stats,
+};
+use std::{
+    hint,
+    num::NonZeroUsize,
+    time::{Duration, Instant},
+};
+
+/// Arguments for the `estimate` command.
+#[derive(Clone, clap::Args)]
+#[command(group(ArgGroup::new("pattern").required(true).multiple(true)))]
+pub struct Args {
+    /// The number of parallel threads to benchmark. Defaults to the number of
+    /// CPUs.
+    #[arg(short = 'n', long, default_value_t = num_cpus::get())]
+    threads: usize,
+
+    #[command(flatten)]
+    pattern: PatternArgs,
+
+    /// How long to benchmark the search for, for example '3s' or '1m'.
+    #[arg(long, default_value = "3s")]
+    benchmark: Timeout,
+}
+
+/// Benchmarks the search and prints the time estimates for the pattern.
+pub fn run(args: Args) {
+    let threads = NonZeroUsize::new(args.threads).unwrap_or(NonZeroUsize::MIN);
+    let patterns = args.pattern.patterns();
+    let (estimate, rate) = if args.pattern.checksum {
+        let patterns = Patterns::new(patterns.into_iter().map(Checksum::new).collect());
+        (
+            Estimate::new(&patterns),
+            benchmark(patterns, threads, args.benchmark.0),
+        )
+    } else {
+        let patterns = Patterns::new(patterns);
+        (
+            Estimate::new(&patterns),
+            benchmark(patterns, threads, args.benchmark.0),
+        )
+    };
+
+    let time = |attempts: f64| Time(Duration::from_secs_f64((attempts / rate).min(1e18)));
+    println!("difficulty:  {} attempts", Si(estimate.expected()));
+    println!("threads:     {threads}");
+    println!("hash rate:   {}H/s", Si(rate));
+    println!("expected:    {}", time(estimate.expected()));
+    println!("50%:         {}", time(estimate.percentile(0.5)));
+    println!("99%:         {}", time(estimate.percentile(0.99)));
+}
+
+/// Runs the search on the specified number of threads for the specified
+/// duration and returns the number of attempts per second.
+///
+/// The benchmark goes through the same parallel search as the actual search,
+/// so that it also measures the batched SIMD hashing.
+fn benchmark<M>(matcher: M, threads: NonZeroUsize, duration: Duration) -> f64
+where
+    M: Matcher + Sync,
+{
+    // The search speed does not depend on the owners, so benchmark with a
+    // placeholder Safe on the canonical deployment.
+    let details = Chain::ethereum()
+        .details()
+        .expect("missing Ethereum deployment");
+    let deployment = details.deployment();
+    let config = Configuration {
+        proxy: config::Proxy {
+            factory: deployment.safe_proxy_factory,
+            init_code: deployment.safe_proxy_init_code.to_vec(),
+            singleton: deployment.safe,
+            chain_specific: None,
+        },
+        account: config::Account {
+            owners: vec![address!(nz "5afe5afe5afe5afe5afe5afe5afe5afe5afe5afe")],
+            threshold: 1,
+            setup: None,
+            fallback_handler: None,
+            identifier: None,
+        },
+    };
+
+    // Stopping on matches would measure restarting the threads for easy
+    // patterns, so benchmark a matcher that never matches but still checks
+    // every address against the patterns.
+    let result = deadbeef_core::search_threads(
+        &Safe::new(config),
+        &Benchmark(matcher),
+        Nonces::random(),
+        threads,
+        &CancellationToken::new(),
+        Some(Instant::now() + duration),
+    );
+    result.stats.iter().map(Stats::rate).sum()
+}
+
+/// A matcher that checks addresses against the inner matcher without ever
+/// matching.
+struct Benchmark<M>(M);
+
+impl<M> Matcher for Benchmark<M>
+where
+    M: Matcher,
+{
+    #[inline]
+    fn matches(&self, address: &Address) -> bool {
+        hint::black_box(self.0.matches(address));
+        false
+    }
+
+    fn difficulty(&self) -> f64 {
+        f64::INFINITY
+    }
+}
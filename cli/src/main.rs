@@ -1,19 +1,51 @@
 mod chain;
+mod checkpoint;
+mod create2;
+mod create3;
+mod createx;
 mod deployment;
+mod estimate;
+mod status;
 
-use self::chain::{Chain, Singleton};
+use self::{
+    chain::{Chain, Explorer, Singleton},
+    checkpoint::Checkpoint,
+    deployment::Version,
+    status::Status,
+};
 use chain::Details;
-use clap::Parser;
-use deadbeef_core::{config, Address, Configuration, NonZeroAddress, Safe};
+use clap::{error::ErrorKind, ArgGroup, CommandFactory, Parser, Subcommand, ValueEnum};
+use deadbeef_core::{
+    config,
+    score::{self, Best, LeadingZeros, Limits, RepeatedNibbles, Scorer, ZeroBytes},
+    Address, Attempts, CancellationToken, Checksum, Configuration, Deployment, Matcher, Nibbles,
+    NonZeroAddress, Nonces, Outcome, Parallel, Pattern, Patterns, Safe,
+};
 use hex::FromHexError;
-use std::{num::NonZeroUsize, process, str::FromStr, sync::mpsc, thread};
+use std::{
+    cell::RefCell,
+    fmt::{self, Display, Formatter},
+    num::{NonZeroUsize, ParseIntError},
+    path::PathBuf,
+    process,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
 /// Generate vanity addresses for Safe deployments.
 #[derive(Clone, Parser)]
+#[command(
+    group(ArgGroup::new("pattern").required(true).multiple(true)),
+    group(ArgGroup::new("limits").args(["min_score", "duration"]).multiple(true)),
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 struct Args {
-    /// The number of parallel threads to use. Defaults to the number of CPUs.
-    #[arg(short = 'n', long, default_value_t = num_cpus::get())]
-    threads: usize,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    search: SearchArgs,
 
     /// Safe owners.
     ///
@@ -26,9 +58,38 @@ struct Args {
     #[arg(short, long, default_value_t = 1)]
     threshold: usize,
 
-    /// The prefix to look for.
-    #[arg(short, long)]
-    prefix: Hex,
+    /// Search for the best scoring address instead of a pattern.
+    ///
+    /// The search runs until '--min-score' is reached or the '--duration'
+    /// time budget runs out, printing each new best address along the way.
+    /// At least one of them must be specified.
+    #[arg(
+        long,
+        group = "pattern",
+        requires = "limits",
+        conflicts_with_all = [
+            "prefix",
+            "suffix",
+            "masked",
+            "checksum",
+            "all",
+            "count",
+            "continuous",
+            "checkpoint",
+            "resume",
+        ],
+    )]
+    score: Option<ScoreKind>,
+
+    /// Stop the scoring search once an address with at least this score has
+    /// been found.
+    #[arg(long, requires = "score")]
+    min_score: Option<u32>,
+
+    /// The time budget for the scoring search, for example '90s', '30m',
+    /// '12h' or '2d'.
+    #[arg(long, requires = "score")]
+    duration: Option<Timeout>,
 
     /// The chain ID to find a vanity Safe address for. If the chain is not
     /// supported, then all of '--proxy-factory', '--proxy-init-code', and
@@ -36,6 +97,19 @@ struct Args {
     #[arg(short, long, default_value_t = Chain::ethereum())]
     chain: Chain,
 
+    /// The Safe version to deploy. Defaults to the latest version deployed on
+    /// the chain.
+    #[arg(long)]
+    safe_version: Option<Version>,
+
+    /// Create the Safe with `createChainSpecificProxyWithNonce`, which mixes
+    /// the chain ID into the salt so that the Safe address is unique to the
+    /// chain.
+    ///
+    /// This requires a v1.4.1 or later proxy factory.
+    #[arg(long)]
+    chain_specific: bool,
+
     /// Override for the `SafeProxyFactory` address.
     #[arg(long)]
     proxy_factory: Option<NonZeroAddress>,
@@ -72,7 +146,7 @@ struct Args {
     /// Quiet mode.
     ///
     /// Only output the transaction calldata without any extra information.
-    #[arg(short, long, conflicts_with = "params")]
+    #[arg(short, long)]
     quiet: bool,
 
     /// Parameters mode.
@@ -83,6 +157,242 @@ struct Args {
     params: bool,
 }
 
+/// Additional commands.
+#[derive(Clone, Subcommand)]
+enum Command {
+    /// Estimate how long it takes to find an address matching a pattern on
+    /// this machine.
+    Estimate(estimate::Args),
+    /// Generate vanity addresses for contracts deployed with `CREATE2` through
+    /// an arbitrary factory, by mining the raw 32-byte salt.
+    Create2(Box<create2::Args>),
+    /// Generate vanity addresses for contracts deployed with `CREATE3` through
+    /// an arbitrary factory, by mining the salt of the proxy it deploys.
+    Create3(Box<create3::Args>),
+    /// Generate vanity addresses for contracts deployed through the CreateX
+    /// factory, by mining the salt that CreateX guards.
+    Createx(Box<createx::Args>),
+}
+
+/// Arguments for searching for addresses matching patterns, shared by the
+/// search commands.
+#[derive(Clone, clap::Args)]
+struct SearchArgs {
+    /// The number of parallel threads to use. Defaults to the number of CPUs.
+    #[arg(short = 'n', long, default_value_t = num_cpus::get())]
+    threads: usize,
+
+    #[command(flatten)]
+    pattern: PatternArgs,
+
+    /// Keep searching until each of the patterns has its own match.
+    #[arg(long, conflicts_with_all = ["count", "continuous"])]
+    all: bool,
+
+    /// The number of matches to find.
+    ///
+    /// Matches are printed as soon as they are found, which allows collecting
+    /// a shortlist of vanity addresses to choose from.
+    #[arg(long, default_value_t = 1, conflicts_with = "continuous")]
+    count: usize,
+
+    /// Continuous mode.
+    ///
+    /// Keep searching indefinitely, printing every match as it is found.
+    #[arg(long)]
+    continuous: bool,
+
+    /// The seed for the salt nonces. Defaults to a random seed.
+    ///
+    /// Salt nonces are made up of the seed in the upper 128 bits and a counter
+    /// in the lower 128 bits, so searches with the same seed are reproducible.
+    #[arg(long)]
+    seed: Option<Uint>,
+
+    /// The counter to start the salt nonce search from.
+    #[arg(long, default_value_t = Uint(0))]
+    start_nonce: Uint,
+
+    /// Only search a part of the salt nonce space, specified as 'i/n' for the
+    /// i-th of n disjoint shards (starting from 0).
+    ///
+    /// This allows splitting a search with the same seed across machines.
+    #[arg(long, default_value_t = Shard(0, 1))]
+    shard: Shard,
+
+    /// Periodically write the search progress to a checkpoint file, so that
+    /// the search can be continued later with '--resume'.
+    #[arg(long)]
+    checkpoint: Option<PathBuf>,
+
+    /// Resume the search from a checkpoint file, skipping the salt nonces that
+    /// were already searched. The checkpoint file continues to be updated.
+    ///
+    /// The deployment configuration and pattern must be the same as for the
+    /// original search. The number of threads is taken from the checkpoint.
+    #[arg(
+        long,
+        conflicts_with_all = ["checkpoint", "seed", "start_nonce", "shard"],
+    )]
+    resume: Option<PathBuf>,
+
+    /// The interval at which to write checkpoints, for example '90s' or '5m'.
+    #[arg(long, default_value = "1m")]
+    checkpoint_interval: Timeout,
+}
+
+/// Arguments for specifying the address patterns to look for.
+#[derive(Clone, clap::Args)]
+struct PatternArgs {
+    /// The prefix to look for.
+    ///
+    /// The prefix may have an odd number of hex characters.
+    #[arg(short, long, group = "pattern")]
+    prefix: Option<Nibbles>,
+
+    /// The suffix to look for.
+    ///
+    /// Can be combined with '--prefix' in order to look for addresses that
+    /// match at both ends.
+    #[arg(short, long, group = "pattern")]
+    suffix: Option<Nibbles>,
+
+    /// A masked pattern to look for.
+    ///
+    /// Use '?' as a wildcard for any nibble of the address and '..' to fill
+    /// the rest of the address with wildcards, for example '0xdead????beef' or
+    /// '0x00..00'. Can be combined with '--prefix' and '--suffix'.
+    ///
+    /// Can be specified multiple times in order to search for any one of
+    /// multiple patterns at once.
+    #[arg(long = "pattern", group = "pattern", num_args = 1..)]
+    masked: Vec<Pattern>,
+
+    /// Match the letter case of the pattern against the EIP-55 checksummed
+    /// address.
+    ///
+    /// Each letter in the pattern doubles the expected search time.
+    #[arg(long)]
+    checksum: bool,
+}
+
+impl PatternArgs {
+    /// Returns the patterns to look for.
+    ///
+    /// The process exits with an error if the prefix, suffix and masked
+    /// patterns do not combine into valid patterns.
+    fn patterns(&self) -> Vec<Pattern> {
+        let patterns = Pattern::new(
+            self.prefix.clone().unwrap_or_default(),
+            self.suffix.clone().unwrap_or_default(),
+        )
+        .and_then(|pattern| {
+            if self.masked.is_empty() {
+                Ok(vec![pattern])
+            } else {
+                self.masked
+                    .iter()
+                    .map(|masked| pattern.merge(masked))
+                    .collect()
+            }
+        });
+        match patterns {
+            Ok(patterns) => patterns,
+            Err(err) => {
+                eprintln!("invalid pattern: {err}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Built-in address scorers.
+#[derive(Clone, Copy, ValueEnum)]
+enum ScoreKind {
+    /// The number of leading zero nibbles.
+    LeadingZeros,
+    /// The total number of zero bytes.
+    ZeroBytes,
+    /// The longest run of a single repeated nibble.
+    RepeatedNibbles,
+}
+
+impl ScoreKind {
+    fn scorer(self) -> Box<dyn Scorer + Sync> {
+        match self {
+            Self::LeadingZeros => Box::new(LeadingZeros),
+            Self::ZeroBytes => Box::new(ZeroBytes),
+            Self::RepeatedNibbles => Box::new(RepeatedNibbles),
+        }
+    }
+}
+
+/// Helper type for parsing durations from the command line.
+#[derive(Clone, Copy)]
+struct Timeout(Duration);
+
+impl FromStr for Timeout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (s, unit) = match s.char_indices().last() {
+            Some((i, 's')) => (&s[..i], 1),
+            Some((i, 'm')) => (&s[..i], 60),
+            Some((i, 'h')) => (&s[..i], 60 * 60),
+            Some((i, 'd')) => (&s[..i], 24 * 60 * 60),
+            _ => (s, 1),
+        };
+        let value = s.parse::<u64>().map_err(|err| err.to_string())?;
+        let secs = value.checked_mul(unit).ok_or("duration too long")?;
+        Ok(Self(Duration::from_secs(secs)))
+    }
+}
+
+/// Helper type for parsing 128-bit integers from the command line.
+#[derive(Clone, Copy)]
+struct Uint(u128);
+
+impl Display for Uint {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Uint {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("0x") {
+            Some(s) => u128::from_str_radix(s, 16).map(Uint),
+            None => s.parse().map(Uint),
+        }
+    }
+}
+
+/// Helper type for parsing salt nonce space shards from the command line.
+#[derive(Clone, Copy)]
+struct Shard(usize, usize);
+
+impl Display for Shard {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.0, self.1)
+    }
+}
+
+impl FromStr for Shard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (index, count) = s.split_once('/').ok_or("expected 'i/n'")?;
+        let index = index.parse::<usize>().map_err(|err| err.to_string())?;
+        let count = count.parse::<usize>().map_err(|err| err.to_string())?;
+        if index >= count {
+            return Err(format!("shard {index} out of range for {count} shards"));
+        }
+        Ok(Self(index, count))
+    }
+}
+
 /// Helper type for parsing hexadecimal byte input from the command line.
 #[derive(Clone)]
 struct Hex(Vec<u8>);
@@ -101,15 +411,58 @@ impl FromStr for Hex {
     }
 }
 
+/// Helper type for parsing 32-byte hexadecimal values from the command line.
+#[derive(Clone, Copy)]
+struct Word([u8; 32]);
+
+impl FromStr for Word {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Hex(bytes) = s.parse().map_err(|err: FromHexError| err.to_string())?;
+        bytes
+            .try_into()
+            .map(Word)
+            .map_err(|bytes: Vec<u8>| format!("expected 32 bytes but got {}", bytes.len()))
+    }
+}
+
 fn main() {
     let args = Args::parse();
+    match args.command {
+        Some(Command::Estimate(args)) => return estimate::run(args),
+        Some(Command::Create2(args)) => return create2::run(*args),
+        Some(Command::Create3(args)) => return create3::run(*args),
+        Some(Command::Createx(args)) => return createx::run(*args),
+        None => {}
+    }
 
-    let threads = NonZeroUsize::new(args.threads);
+    if let Some(reason) = args.chain.unsupported() {
+        eprintln!("{reason}");
+        process::exit(1);
+    }
+
+    let threads = NonZeroUsize::new(args.search.threads);
     let chain = args.chain.details();
     let config = chain
         .as_ref()
         .map(|details| {
-            let contracts = details.deployment();
+            let version = args
+                .safe_version
+                .unwrap_or_else(|| details.latest_version());
+            let contracts = details.version(version).unwrap_or_else(|| {
+                let versions = details.versions().map(|v| v.to_string());
+                eprintln!(
+                    "Safe v{version} is not deployed on chain {}, available versions: {}",
+                    args.chain,
+                    versions.collect::<Vec<_>>().join(", "),
+                );
+                process::exit(1);
+            });
+            if args.chain_specific && !version.has_chain_specific_proxies() {
+                eprintln!("the Safe v{version} proxy factory cannot create chain-specific proxies");
+                process::exit(1);
+            }
             let setup = args
                 .safe_to_l2_setup
                 .unwrap_or(contracts.safe_to_l2_setup)
@@ -136,6 +489,7 @@ fn main() {
                             (_, Singleton::SafeL2) => contracts.safe_l2,
                         }
                     }),
+                    chain_specific: args.chain_specific.then(|| args.chain.id()),
                 },
                 account: config::Account {
                     owners: args.owners.clone(),
@@ -152,8 +506,9 @@ fn main() {
             Some(Configuration {
                 proxy: config::Proxy {
                     factory: args.proxy_factory?,
-                    init_code: args.proxy_init_code?.cloned(),
+                    init_code: args.proxy_init_code.as_ref()?.cloned(),
                     singleton: args.singleton?,
+                    chain_specific: args.chain_specific.then(|| args.chain.id()),
                 },
                 account: config::Account {
                     owners: args.owners.clone(),
@@ -178,34 +533,350 @@ fn main() {
         .expect("unsupported chain");
     let explorer = chain.as_ref().map(Details::explorer);
 
-    let setup = || (Safe::new(config.clone()), args.prefix.0.clone());
-    let safe = if let Some(threads) = threads {
-        let (sender, receiver) = mpsc::channel();
-        let _threads = (0..threads.get())
-            .map(|_| {
-                thread::spawn({
-                    let (mut safe, prefix) = setup();
-                    let result = sender.clone();
-                    move || {
-                        deadbeef_core::search(&mut safe, &prefix);
-                        let _ = result.send(safe);
-                    }
+    if let Some(kind) = args.score {
+        let limits = Limits {
+            min_score: args.min_score,
+            deadline: args.duration.map(|Timeout(duration)| {
+                Instant::now().checked_add(duration).unwrap_or_else(|| {
+                    Args::command()
+                        .error(ErrorKind::ValueValidation, "--duration is too long")
+                        .exit()
                 })
-            })
-            .collect::<Vec<_>>();
-        receiver.recv().expect("missing result")
+            }),
+        };
+        let nonces = args
+            .search
+            .seed
+            .map(|Uint(seed)| Nonces::new(seed))
+            .unwrap_or_else(Nonces::random)
+            .starting_at(args.search.start_nonce.0)
+            .shard(args.search.shard.0, args.search.shard.1);
+        let attempts = Attempts::new();
+        let Some((score, safe)) = search_score(
+            &config,
+            &*kind.scorer(),
+            nonces,
+            &limits,
+            threads,
+            &attempts,
+        ) else {
+            eprintln!("salt nonce range exhausted");
+            process::exit(1);
+        };
+        if !args.quiet {
+            println!("score:       {score}");
+        }
+        print(&args, &config, explorer, &[], 0, &safe, attempts.get());
+        process::exit(0);
+    }
+
+    let safe = Safe::new(config.clone());
+    run(
+        &args.search,
+        safe,
+        None,
+        args.quiet,
+        |safe, patterns, index, attempts| {
+            print(&args, &config, explorer, patterns, index, safe, attempts)
+        },
+    );
+}
+
+/// Searches for deployments matching the patterns until enough matches were
+/// found, printing each of them with the specified function.
+///
+/// The search is restricted to the specified salt nonces if the deployment
+/// only allows some salts. The search progress is checkpointed as configured
+/// by the arguments, and the process exits with an error if the salt nonce
+/// range is exhausted.
+fn run<D: Deployment>(
+    args: &SearchArgs,
+    deployment: D,
+    salts: Option<Nonces>,
+    quiet: bool,
+    print: impl Fn(&D, &[Pattern], usize, u64),
+) {
+    let threads = NonZeroUsize::new(args.threads);
+    let patterns = args.pattern.patterns();
+
+    let digest = deployment.digest();
+    let pattern_strings = patterns.iter().map(Pattern::to_string).collect::<Vec<_>>();
+    let mut checkpoint = match &args.resume {
+        Some(path) => {
+            let checkpoint = match Checkpoint::load(path) {
+                Ok(checkpoint) => checkpoint,
+                Err(err) => {
+                    eprintln!("invalid checkpoint {}: {err}", path.display());
+                    process::exit(1);
+                }
+            };
+            if checkpoint.config != digest {
+                eprintln!("checkpoint is for a different deployment");
+                process::exit(1);
+            }
+            if checkpoint.patterns != pattern_strings
+                || checkpoint.checksum != args.pattern.checksum
+            {
+                eprintln!("checkpoint is for a different pattern");
+                process::exit(1);
+            }
+            checkpoint
+        }
+        None => {
+            let nonces = match salts {
+                Some(_) if args.seed.is_some() => {
+                    eprintln!("the salt nonce seed is fixed by the deployer's salt rules");
+                    process::exit(1);
+                }
+                Some(salts) => salts.starting_at(args.start_nonce.0.max(salts.start())),
+                None => args
+                    .seed
+                    .map(|Uint(seed)| Nonces::new(seed))
+                    .unwrap_or_else(Nonces::random)
+                    .starting_at(args.start_nonce.0),
+            }
+            .shard(args.shard.0, args.shard.1);
+            let workers = threads.map(NonZeroUsize::get).unwrap_or(1);
+            Checkpoint {
+                config: digest,
+                patterns: pattern_strings,
+                checksum: args.pattern.checksum,
+                seed: nonces.seed(),
+                workers: (0..workers)
+                    .map(|worker| {
+                        let nonces = nonces.shard(worker, workers);
+                        checkpoint::Worker {
+                            start: nonces.start(),
+                            next: nonces.start(),
+                            end: nonces.end(),
+                        }
+                    })
+                    .collect(),
+            }
+        }
+    };
+    let checkpoint_path = args.resume.as_ref().or(args.checkpoint.as_ref());
+    let save = |checkpoint: &Checkpoint| {
+        if let Some(path) = checkpoint_path {
+            if let Err(err) = checkpoint.save(path) {
+                eprintln!("failed to write checkpoint {}: {err}", path.display());
+                process::exit(1);
+            }
+        }
+    };
+    // Write the checkpoint before searching, so that an unwritable path is
+    // reported right away instead of after the first checkpoint interval.
+    save(&checkpoint);
+
+    let difficulty = if args.pattern.checksum {
+        Patterns::new(patterns.iter().cloned().map(Checksum::new).collect()).difficulty()
     } else {
-        let (mut safe, prefix) = setup();
-        deadbeef_core::search(&mut safe, &prefix);
-        safe
+        Patterns::new(patterns.clone()).difficulty()
+    };
+    let status = RefCell::new(Status::new(difficulty));
+
+    let mut matched = vec![false; patterns.len()];
+    let mut count = 0;
+    let mut found = |indices: &[usize], deployment: D, attempts: u64| {
+        // With `--all`, an address is reported for the first pattern it
+        // matches that was not already matched, and counts towards every
+        // other unmatched pattern it also matches.
+        let index = if args.all {
+            let unmatched = indices
+                .iter()
+                .copied()
+                .filter(|&index| !matched[index])
+                .collect::<Vec<_>>();
+            let Some(&index) = unmatched.first() else {
+                return true;
+            };
+            for index in unmatched {
+                matched[index] = true;
+            }
+            index
+        } else {
+            indices[0]
+        };
+
+        status.borrow_mut().clear();
+        if count > 0 && !quiet {
+            println!();
+        }
+        print(&deployment, &patterns, index, attempts);
+        count += 1;
+
+        if args.all {
+            !matched.iter().all(|matched| *matched)
+        } else {
+            args.continuous || count < args.count
+        }
+    };
+    let mut ranges = checkpoint.remaining();
+    let mut saved = Instant::now();
+    let mut progress = |remaining: &[Nonces], attempts: u64| {
+        status.borrow_mut().update(attempts);
+        if saved.elapsed() >= args.checkpoint_interval.0 {
+            checkpoint.update(remaining);
+            save(&checkpoint);
+            saved = Instant::now();
+        }
     };
 
+    if args.pattern.checksum {
+        search(
+            &deployment,
+            Patterns::new(patterns.iter().cloned().map(Checksum::new).collect()),
+            &mut ranges,
+            threads.is_none(),
+            &mut found,
+            &mut progress,
+        );
+    } else {
+        search(
+            &deployment,
+            Patterns::new(patterns.clone()),
+            &mut ranges,
+            threads.is_none(),
+            &mut found,
+            &mut progress,
+        );
+    }
+    status.borrow_mut().clear();
+    checkpoint.update(&ranges);
+    save(&checkpoint);
+
+    if ranges.iter().all(Nonces::is_empty) {
+        eprintln!("salt nonce range exhausted");
+        process::exit(1);
+    }
+}
+
+/// Searches for deployments matching the patterns, calling the specified
+/// function with the indices of the matched patterns, the deployment and the
+/// total number of attempts for every match until it returns `false`.
+///
+/// Each salt nonce range is searched on its own thread, or one after the
+/// other on the current thread when searching inline. The ranges are advanced
+/// as they are searched, and the `progress` function is called about once per
+/// second with the ranges and the total number of attempts.
+fn search<D, M>(
+    deployment: &D,
+    patterns: Patterns<M>,
+    ranges: &mut [Nonces],
+    inline: bool,
+    mut found: impl FnMut(&[usize], D, u64) -> bool,
+    mut progress: impl FnMut(&[Nonces], u64),
+) where
+    D: Deployment,
+    M: AsRef<Pattern> + Matcher + Sync,
+{
+    const POLL: Duration = Duration::from_secs(1);
+
+    let cancellation = CancellationToken::new();
+    let mut attempts = 0;
+    while !ranges.iter().all(Nonces::is_empty) {
+        let deadline = Some(Instant::now() + POLL);
+        let result = if inline {
+            let mut deployment = deployment.clone();
+            let nonces = ranges
+                .iter_mut()
+                .find(|nonces| !nonces.is_empty())
+                .expect("missing salt nonce range");
+            let (outcome, stats) = deadbeef_core::search_until(
+                &mut deployment,
+                &patterns,
+                nonces,
+                &cancellation,
+                deadline,
+            );
+            Parallel {
+                outcome,
+                deployment: matches!(outcome, Outcome::Found(_)).then_some(deployment),
+                stats: vec![stats],
+            }
+        } else {
+            deadbeef_core::search_parallel(deployment, &patterns, ranges, &cancellation, deadline)
+        };
+
+        attempts += result.stats.iter().map(|stats| stats.attempts).sum::<u64>();
+        if let Some(deployment) = result.deployment {
+            let indices = patterns
+                .find_all(&deployment.creation_address())
+                .collect::<Vec<_>>();
+            assert!(!indices.is_empty(), "deployment does not match any pattern");
+            if !found(&indices, deployment, attempts) {
+                return;
+            }
+        }
+        progress(ranges, attempts);
+    }
+}
+
+/// Searches for the best scoring Safe until the limits are reached, printing
+/// each new best Safe to `stderr` as it is found. Returns [`None`] if the salt
+/// nonce range is exhausted before any Safe is scored.
+///
+/// The salt nonce range is split between the threads, or searched on the
+/// current thread when searching inline.
+fn search_score(
+    config: &Configuration,
+    scorer: &(dyn Scorer + Sync),
+    nonces: Nonces,
+    limits: &Limits,
+    threads: Option<NonZeroUsize>,
+    attempts: &Attempts,
+) -> Option<(u32, Safe)> {
+    let best = Best::new();
+    let report = |score: u32, safe: &Safe| {
+        eprintln!(
+            "score {score}: {} (salt nonce 0x{})",
+            safe.creation_address(),
+            hex::encode(safe.salt_nonce()),
+        );
+    };
+
+    let mut safe = Safe::new(config.clone());
+    if let Some(threads) = threads {
+        let threads = threads.get();
+        let mut ranges = (0..threads)
+            .map(|thread| nonces.shard(thread, threads))
+            .collect::<Vec<_>>();
+        score::search_parallel(&safe, scorer, &mut ranges, &best, limits, attempts, report);
+    } else {
+        let mut nonces = nonces;
+        score::search(
+            &mut safe,
+            scorer,
+            &mut nonces,
+            &best,
+            limits,
+            attempts,
+            report,
+        );
+    }
+
+    best.get()
+}
+
+/// Prints the result of a vanity Safe search.
+fn print(
+    args: &Args,
+    config: &Configuration,
+    explorer: Option<&Explorer>,
+    patterns: &[Pattern],
+    index: usize,
+    safe: &Safe,
+    attempts: u64,
+) {
     let transaction = safe.transaction();
 
     if args.quiet {
         println!("0x{}", hex::encode(&transaction.calldata));
     } else if args.params {
+        // Explorer links point to `createProxyWithNonce`, so they are only
+        // used for Safes that are not chain-specific.
         let factory = explorer
+            .filter(|_| config.proxy.chain_specific.is_none())
             .map(|explorer| explorer.create_proxy_with_nonce_url(config.proxy.factory.get()))
             .unwrap_or_else(|| config.proxy.factory.to_string());
 
@@ -227,11 +898,18 @@ fn main() {
             .map(NonZeroAddress::get)
             .unwrap_or_default();
 
+        if patterns.len() > 1 {
+            println!("pattern:     {}", patterns[index]);
+        }
         println!("address:     {}", safe.creation_address());
         println!("factory:     {}", config.proxy.factory);
         println!("singleton:   {}", config.proxy.singleton);
         println!("initializer: 0x{}", hex::encode(safe.initializer()));
         println!("salt nonce:  0x{}", hex::encode(safe.salt_nonce()));
+        let (seed, counter) = Nonces::split(safe.salt_nonce());
+        println!("seed:        {:#x}", seed);
+        println!("counter:     {}", counter);
+        println!("attempts:    {}", attempts);
         println!("---");
         println!("owners:      {}", config.account.owners[0]);
         for owner in &args.owners[1..] {
@@ -244,6 +922,4 @@ fn main() {
         println!("---");
         println!("calldata:    0x{}", hex::encode(&transaction.calldata));
     }
-
-    process::exit(0);
 }
@@ -0,0 +1,122 @@
+//! Module for reporting search progress on `stderr`.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    io::{self, IsTerminal as _, Write as _},
+    time::{Duration, Instant},
+};
+
+/// A live status line with the search hash rate, attempts and ETA.
+pub struct Status {
+    enabled: bool,
+    difficulty: f64,
+    started: Instant,
+    printed: Option<Instant>,
+}
+
+impl Status {
+    /// Creates a new status line for a pattern with the specified difficulty.
+    ///
+    /// The status line is only shown when `stderr` is a terminal.
+    pub fn new(difficulty: f64) -> Self {
+        Self {
+            enabled: io::stderr().is_terminal(),
+            difficulty,
+            started: Instant::now(),
+            printed: None,
+        }
+    }
+
+    /// Updates the status line with the total number of attempts so far. The
+    /// line is redrawn at most once per second.
+    pub fn update(&mut self, attempts: u64) {
+        if !self.enabled
+            || self
+                .printed
+                .is_some_and(|printed| printed.elapsed() < Duration::from_secs(1))
+        {
+            return;
+        }
+
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let rate = attempts as f64 / elapsed;
+        // The search is memoryless, so the expected number of attempts until
+        // the next match is always the difficulty, however long it has run.
+        let eta = if rate > 0. {
+            Time(Duration::from_secs_f64(
+                (self.difficulty / rate).min(u64::MAX as f64),
+            ))
+            .to_string()
+        } else {
+            "-".to_owned()
+        };
+
+        eprint!(
+            "\r\x1b[K{}H/s, {} attempts, ETA {eta}",
+            Si(rate),
+            Si(attempts as _),
+        );
+        let _ = io::stderr().flush();
+        self.printed = Some(Instant::now());
+    }
+
+    /// Clears the status line.
+    pub fn clear(&mut self) {
+        if self.enabled && self.printed.take().is_some() {
+            eprint!("\r\x1b[K");
+        }
+    }
+}
+
+/// A number formatted with an SI prefix.
+pub struct Si(pub f64);
+
+impl Display for Si {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let (value, prefix) = [(1e12, "T"), (1e9, "G"), (1e6, "M"), (1e3, "k")]
+            .into_iter()
+            .find(|(scale, _)| self.0 >= *scale)
+            .map(|(scale, prefix)| (self.0 / scale, prefix))
+            .unwrap_or((self.0, ""));
+        write!(f, "{value:.1}{prefix}")
+    }
+}
+
+/// A duration formatted in days, hours, minutes and seconds.
+pub struct Time(pub Duration);
+
+impl Display for Time {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let secs = self.0.as_secs();
+        let (d, h, m, s) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+        match (d, h, m) {
+            (0, 0, 0) => write!(f, "{s}s"),
+            (0, 0, _) => write!(f, "{m}m {s}s"),
+            (0, _, _) => write!(f, "{h}h {m}m"),
+            _ => write!(f, "{d}d {h}h"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formatting() {
+        assert_eq!(Si(999.).to_string(), "999.0");
+        assert_eq!(Si(12_345_678.).to_string(), "12.3M");
+        assert_eq!(Si(4.2e12).to_string(), "4.2T");
+
+        assert_eq!(Time(Duration::from_secs(42)).to_string(), "42s");
+        assert_eq!(Time(Duration::from_secs(90)).to_string(), "1m 30s");
+        assert_eq!(
+            Time(Duration::from_secs(3 * 3600 + 61)).to_string(),
+            "3h 1m"
+        );
+        assert_eq!(
+            Time(Duration::from_secs(2 * 86400 + 7200)).to_string(),
+            "2d 2h"
+        );
+    }
+}
@@ -16,6 +16,15 @@ impl Address {
         Self([0; 20])
     }
 
+    /// Returns the Keccak-256 digest of the lower case hex representation of
+    /// the address used for [EIP-55](https://eips.ethereum.org/EIPS/eip-55)
+    /// checksums.
+    pub(crate) fn checksum_digest(&self) -> [u8; 32] {
+        let mut buf = [0_u8; 40];
+        hex::encode_to_slice(self.0.as_slice(), &mut buf).expect("error encoding hex");
+        keccak::v256(&buf)
+    }
+
     /// Returns `Some(self)` if the address is non-zero.
     pub fn non_zero(self) -> Option<NonZeroAddress> {
         if self == Self::zero() {
@@ -51,7 +60,7 @@ impl Display for Address {
         let addr = &mut buf[2..];
         hex::encode_to_slice(self.0.as_slice(), addr).expect("error decoding hex");
 
-        let digest = keccak::v256(addr);
+        let digest = self.checksum_digest();
         for i in 0..addr.len() {
             let byte = digest[i / 2];
             let nibble = 0xf & if i % 2 == 0 { byte >> 4 } else { byte };
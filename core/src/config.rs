@@ -13,6 +13,27 @@ pub struct Configuration {
     pub account: Account,
 }
 
+impl Configuration {
+    /// Returns a digest of the configuration.
+    ///
+    /// Two configurations with the same digest produce the same Safe address
+    /// for every salt nonce.
+    pub fn digest(&self) -> [u8; 32] {
+        let code_hash = self.proxy.init_code_hash();
+        let factory = self.proxy.factory.get().0;
+        let initializer_hash = keccak::v256(&self.account.initializer());
+        match self.proxy.chain_specific {
+            None => keccak::v256_chunked(&[&factory, &code_hash, &initializer_hash]),
+            Some(chain_id) => keccak::v256_chunked(&[
+                &factory,
+                &code_hash,
+                &initializer_hash,
+                &abi::uint(chain_id),
+            ]),
+        }
+    }
+}
+
 /// The Safe proxy configuration.
 #[derive(Clone)]
 pub struct Proxy {
@@ -22,6 +43,10 @@ pub struct Proxy {
     pub init_code: Vec<u8>,
     /// The `Safe` singleton implementation address.
     pub singleton: NonZeroAddress,
+    /// The chain ID for chain-specific proxies created with
+    /// `createChainSpecificProxyWithNonce`, which mixes it into the salt.
+    /// Proxies are created with `createProxyWithNonce` if this is `None`.
+    pub chain_specific: Option<u128>,
 }
 
 impl Proxy {
@@ -33,8 +58,29 @@ impl Proxy {
     /// Returns the calldata for the `createProxyWithNonce` call on the proxy
     /// factory.
     pub fn create_proxy_with_nonce(&self, initializer: &[u8], salt_nonce: [u8; 32]) -> Vec<u8> {
+        self.encode_create_proxy(hex!("1688f0b9"), initializer, salt_nonce)
+    }
+
+    /// Returns the calldata for the `createChainSpecificProxyWithNonce` call
+    /// on the proxy factory.
+    pub fn create_chain_specific_proxy_with_nonce(
+        &self,
+        initializer: &[u8],
+        salt_nonce: [u8; 32],
+    ) -> Vec<u8> {
+        self.encode_create_proxy(hex!("ec9e80bb"), initializer, salt_nonce)
+    }
+
+    /// Encodes a proxy factory call with `(singleton, initializer, saltNonce)`
+    /// parameters.
+    fn encode_create_proxy(
+        &self,
+        selector: [u8; 4],
+        initializer: &[u8],
+        salt_nonce: [u8; 32],
+    ) -> Vec<u8> {
         let mut buffer = Vec::new();
-        buffer.extend_from_slice(&hex!("1688f0b9"));
+        buffer.extend_from_slice(&selector);
         buffer.extend_from_slice(&abi::addr(self.singleton.get()));
         buffer.extend_from_slice(&abi::num(0x60)); // initializer.offset
         buffer.extend_from_slice(&salt_nonce);
@@ -115,7 +161,7 @@ impl SafeToL2Setup {
 }
 
 /// Poor man's Solidity ABI encoding.
-mod abi {
+pub(crate) mod abi {
     use crate::address::Address;
     use std::mem;
 
@@ -125,6 +171,12 @@ mod abi {
         b
     }
 
+    pub fn uint(a: u128) -> [u8; 32] {
+        let mut b = [0_u8; 32];
+        b[16..].copy_from_slice(&a.to_be_bytes());
+        b
+    }
+
     pub fn addr(a: Address) -> [u8; 32] {
         let mut b = [0_u8; 32];
         b[12..].copy_from_slice(&a.0);
@@ -1,43 +1,97 @@
 //! Module implementing `CREATE2` deterministic address computation logic.
 
-use crate::{address::Address, keccak};
+use crate::{
+    address::Address,
+    deployment::Deployment,
+    keccak::{self, simd},
+};
 
 /// `CREATE2` parameters.
 #[derive(Clone, Debug)]
-pub struct Create2([u8; 85]);
+pub struct Create2 {
+    factory: Address,
+    salt: [u8; 32],
+    init_code_hash: [u8; 32],
+    hasher: keccak::Create2Hasher,
+}
 
 impl Create2 {
     /// Creates a new instance with the specified parameters.
-    pub fn new(factory: Address, salt: [u8; 32], init_code: [u8; 32]) -> Self {
-        let mut create2 = Self([0xff_u8; 85]);
-        *create2.factory_mut() = factory;
-        *create2.salt_mut() = salt;
-        *create2.init_code_mut() = init_code;
-        create2
+    pub fn new(factory: Address, salt: [u8; 32], init_code_hash: [u8; 32]) -> Self {
+        let mut input = [0xff_u8; 85];
+        input[1..21].copy_from_slice(&factory.0);
+        input[21..53].copy_from_slice(&salt);
+        input[53..85].copy_from_slice(&init_code_hash);
+        Self {
+            factory,
+            salt,
+            init_code_hash,
+            hasher: keccak::Create2Hasher::new(&input),
+        }
+    }
+
+    /// Creates a new instance for the specified init code.
+    pub fn with_init_code(factory: Address, salt: [u8; 32], init_code: &[u8]) -> Self {
+        Self::new(factory, salt, keccak::v256(init_code))
+    }
+
+    /// Returns the factory address.
+    pub fn factory(&self) -> Address {
+        self.factory
     }
 
-    /// Returns the slice representing the factory.
-    pub fn factory_mut(&mut self) -> &mut Address {
-        unsafe { &mut *self.0.get_unchecked_mut(1..21).as_mut_ptr().cast() }
+    /// Returns the `CREATE2` salt.
+    pub fn salt(&self) -> [u8; 32] {
+        self.salt
     }
 
-    /// Returns the slice representing the factory.
-    pub fn salt_mut(&mut self) -> &mut [u8; 32] {
-        unsafe { &mut *self.0.get_unchecked_mut(21..53).as_mut_ptr().cast() }
+    /// Returns the init code hash.
+    pub fn init_code_hash(&self) -> [u8; 32] {
+        self.init_code_hash
     }
 
-    /// Returns the slice representing the factory.
-    pub fn init_code_mut(&mut self) -> &mut [u8; 32] {
-        unsafe { &mut *self.0.get_unchecked_mut(53..85).as_mut_ptr().cast() }
+    /// Sets the `CREATE2` salt.
+    pub fn set_salt(&mut self, salt: &[u8; 32]) {
+        self.salt = *salt;
+        self.hasher.set_salt(salt);
+    }
+
+    /// Returns the specialised hasher for the `CREATE2` parameters.
+    pub(crate) fn hasher(&self) -> &keccak::Create2Hasher {
+        &self.hasher
     }
 
     /// Returns the deterministic address for the `CREATE2` parameters.
     pub fn creation_address(&self) -> Address {
-        let digest = keccak::v256(&self.0);
+        let digest = self.hasher.hash();
         Address(unsafe { *digest.get_unchecked(12..32).as_ptr().cast() })
     }
 }
 
+/// Mining a `CREATE2` deployment directly uses the salt as the salt nonce.
+impl Deployment for Create2 {
+    fn digest(&self) -> [u8; 32] {
+        keccak::v256_chunked(&[&self.factory.0, &self.init_code_hash])
+    }
+
+    fn salt_nonce(&self) -> [u8; 32] {
+        self.salt
+    }
+
+    fn update_salt_nonce(&mut self, f: impl FnOnce(&mut [u8; 32])) {
+        f(&mut self.salt);
+        self.hasher.set_salt(&self.salt);
+    }
+
+    fn creation_address(&self) -> Address {
+        Create2::creation_address(self)
+    }
+
+    fn hasher(&self) -> Option<simd::Hasher> {
+        Some(simd::Hasher::create2(&self.hasher))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
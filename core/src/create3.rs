@@ -0,0 +1,129 @@
+//! Module implementing `CREATE3` deterministic address computation logic.
+//!
+//! A `CREATE3` factory deploys a fixed proxy contract with `CREATE2`, which
+//! in turn deploys the actual contract with `CREATE`. Since the proxy is
+//! freshly deployed, its nonce is always 1 when it creates the contract, so
+//! the final address only depends on the factory, the salt and the proxy.
+
+use crate::{address::Address, create2::Create2, deployment::Deployment, keccak};
+use hex_literal::hex;
+
+/// The init code of the minimal proxy used by 0xSequence's `create3`, as well
+/// as by the solmate and Solady `CREATE3` libraries and CreateX, which
+/// deploys the calldata it is called with as init code.
+pub const PROXY_INIT_CODE: [u8; 16] = hex!("67363d3d37363d34f03d5260086018f3");
+
+/// The Keccak-256 hash of [`PROXY_INIT_CODE`].
+pub const PROXY_INIT_CODE_HASH: [u8; 32] =
+    hex!("21c35dbe1b344a2488cf3321d6ce542f8e9f305544ff09e4993a62319a497c1f");
+
+/// `CREATE3` parameters.
+#[derive(Clone, Debug)]
+pub struct Create3(Create2);
+
+impl Create3 {
+    /// Creates a new instance with the specified parameters.
+    pub fn new(factory: Address, salt: [u8; 32], proxy_init_code_hash: [u8; 32]) -> Self {
+        Self(Create2::new(factory, salt, proxy_init_code_hash))
+    }
+
+    /// Returns the factory address.
+    pub fn factory(&self) -> Address {
+        self.0.factory()
+    }
+
+    /// Returns the `CREATE2` salt used for deploying the proxy.
+    pub fn salt(&self) -> [u8; 32] {
+        self.0.salt()
+    }
+
+    /// Returns the proxy init code hash.
+    pub fn proxy_init_code_hash(&self) -> [u8; 32] {
+        self.0.init_code_hash()
+    }
+
+    /// Sets the `CREATE2` salt used for deploying the proxy.
+    pub fn set_salt(&mut self, salt: &[u8; 32]) {
+        self.0.set_salt(salt);
+    }
+
+    /// Returns the deterministic address of the proxy.
+    pub fn proxy_address(&self) -> Address {
+        self.0.creation_address()
+    }
+
+    /// Returns the deterministic address for the `CREATE3` parameters.
+    pub fn creation_address(&self) -> Address {
+        created_by_proxy(self.proxy_address())
+    }
+}
+
+/// Mining a `CREATE3` deployment directly uses the proxy salt as the salt
+/// nonce.
+impl Deployment for Create3 {
+    fn digest(&self) -> [u8; 32] {
+        keccak::v256_chunked(&[b"CREATE3", &self.factory().0, &self.proxy_init_code_hash()])
+    }
+
+    fn salt_nonce(&self) -> [u8; 32] {
+        self.salt()
+    }
+
+    fn update_salt_nonce(&mut self, f: impl FnOnce(&mut [u8; 32])) {
+        let mut salt = self.salt();
+        f(&mut salt);
+        self.set_salt(&salt);
+    }
+
+    fn creation_address(&self) -> Address {
+        Create3::creation_address(self)
+    }
+}
+
+/// Returns the address of the contract created with `CREATE` by a freshly
+/// deployed proxy, that is `keccak256(rlp([proxy, 1]))`.
+fn created_by_proxy(proxy: Address) -> Address {
+    // The RLP encoding of the list is its header `0xc0 + 22`, followed by
+    // the 20-byte address string with header `0x80 + 20`, followed by the
+    // nonce 1, which is encoded as the single byte itself.
+    let mut rlp = [0_u8; 23];
+    rlp[0] = 0xd6;
+    rlp[1] = 0x94;
+    rlp[2..22].copy_from_slice(&proxy.0);
+    rlp[22] = 0x01;
+    let digest = keccak::v256(&rlp);
+    Address(digest[12..].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proxy_init_code_hash() {
+        assert_eq!(keccak::v256(&PROXY_INIT_CODE), PROXY_INIT_CODE_HASH);
+    }
+
+    #[test]
+    fn created_by_proxy_address() {
+        // The second contract created by an account:
+        // <https://ethereum.stackexchange.com/a/761>
+        assert_eq!(
+            created_by_proxy(address!("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0")),
+            address!("343c43a37d37dff08ae8c4a11544c718abb4fcf8"),
+        );
+    }
+
+    #[test]
+    fn computes_deterministic_address() {
+        let create3 = Create3::new(
+            address!("ba5Ed099633D3B313e4D5F7bdc1305d3c28ba5Ed"),
+            [0x5a; 32],
+            PROXY_INIT_CODE_HASH,
+        );
+        let proxy = Create2::with_init_code(create3.factory(), create3.salt(), &PROXY_INIT_CODE)
+            .creation_address();
+        assert_eq!(create3.proxy_address(), proxy);
+        assert_eq!(create3.creation_address(), created_by_proxy(proxy));
+    }
+}
@@ -0,0 +1,347 @@
+//! Module implementing deterministic deployments through the CreateX factory.
+//!
+//! CreateX does not use the salt it is called with directly, instead it
+//! derives a guarded salt from it depending on the protections encoded in its
+//! first 21 bytes: a permissioned deployer (bytes 0 to 19) and a cross-chain
+//! redeploy protection flag (byte 20).
+//!
+//! <https://github.com/pcaversaccio/createx>
+
+use crate::{
+    address::Address,
+    config::abi,
+    create2::Create2,
+    create3::{self, Create3},
+    deployment::Deployment,
+    keccak,
+    nonce::Nonces,
+};
+use hex_literal::hex;
+
+/// The address of the CreateX factory, which is the same on all chains.
+pub const ADDRESS: Address = address!("ba5Ed099633D3B313e4D5F7bdc1305d3c28ba5Ed");
+
+/// Computes the guarded salt for a salt, as CreateX's `_guard` function does
+/// when called by `sender` on the chain with `chain_id`. Returns `None` if the
+/// deployment reverts with `InvalidSalt`.
+pub fn guard(salt: [u8; 32], sender: Address, chain_id: u128) -> Option<[u8; 32]> {
+    let (deployer, flag) = (&salt[..20], salt[20]);
+    let guarded = if deployer == sender.0 {
+        match flag {
+            0x01 => keccak::v256_chunked(&[&abi::addr(sender), &abi::uint(chain_id), &salt]),
+            0x00 => keccak::v256_chunked(&[&abi::addr(sender), &salt]),
+            _ => return None,
+        }
+    } else if deployer == [0; 20] {
+        match flag {
+            0x01 => keccak::v256_chunked(&[&abi::uint(chain_id), &salt]),
+            0x00 => keccak::v256(&salt),
+            _ => return None,
+        }
+    } else {
+        // Salts starting with another address are hashed as is, which also
+        // makes them impossible to front-run on chains other than the one
+        // they were mined for.
+        keccak::v256(&salt)
+    };
+    Some(guarded)
+}
+
+/// The deployment protections encoded in the first 21 bytes of a salt.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Protection {
+    /// The permissioned deployer, that is the only account allowed to deploy
+    /// with the salt. Anyone can deploy if this is `None`.
+    pub caller: Option<Address>,
+    /// The chain ID for cross-chain redeploy protection, which prevents
+    /// deploying to the same address on other chains.
+    pub chain_id: Option<u128>,
+}
+
+impl Protection {
+    /// Returns the salt prefix that enables the protections.
+    pub fn prefix(&self) -> [u8; 21] {
+        let mut prefix = [0; 21];
+        prefix[..20].copy_from_slice(&self.caller.unwrap_or_default().0);
+        prefix[20] = self.chain_id.is_some() as u8;
+        prefix
+    }
+
+    /// Returns the salt nonces that are salts with the protections.
+    pub fn salts(&self) -> Nonces {
+        Nonces::prefixed(&self.prefix())
+    }
+
+    /// Computes the guarded salt for a salt with the protections' prefix.
+    fn guard(&self, salt: &[u8; 32]) -> [u8; 32] {
+        match (self.caller, self.chain_id) {
+            (Some(caller), Some(chain_id)) => {
+                keccak::v256_chunked(&[&abi::addr(caller), &abi::uint(chain_id), salt])
+            }
+            (Some(caller), None) => keccak::v256_chunked(&[&abi::addr(caller), salt]),
+            (None, Some(chain_id)) => keccak::v256_chunked(&[&abi::uint(chain_id), salt]),
+            (None, None) => keccak::v256(salt),
+        }
+    }
+}
+
+/// A CreateX deployment function.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Entrypoint {
+    /// `deployCreate2(bytes32 salt, bytes initCode)`.
+    Create2,
+    /// `deployCreate3(bytes32 salt, bytes initCode)`.
+    Create3,
+}
+
+impl Entrypoint {
+    /// Returns the calldata for deploying the init code with the salt.
+    pub fn calldata(self, salt: [u8; 32], init_code: &[u8]) -> Vec<u8> {
+        let selector = match self {
+            Self::Create2 => hex!("26307668"),
+            Self::Create3 => hex!("9c36a286"),
+        };
+
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(&selector);
+        buffer.extend_from_slice(&salt);
+        buffer.extend_from_slice(&abi::num(0x40)); // initCode.offset
+        buffer.extend_from_slice(&abi::num(init_code.len()));
+        buffer.extend_from_slice(init_code);
+        buffer.extend_from_slice(abi::padding(init_code));
+        buffer
+    }
+}
+
+/// A deployment through CreateX.
+///
+/// The salt nonce is the salt passed to CreateX, and always starts with the
+/// prefix for the deployment's protections.
+#[derive(Clone, Debug)]
+pub struct CreateX {
+    protection: Protection,
+    salt: [u8; 32],
+    target: Target,
+}
+
+/// The underlying deployment performed by CreateX with the guarded salt.
+#[derive(Clone, Debug)]
+enum Target {
+    Create2(Create2),
+    Create3(Create3),
+}
+
+impl CreateX {
+    /// Creates a new `deployCreate2` deployment of the contract with the
+    /// specified init code hash.
+    pub fn create2(protection: Protection, init_code_hash: [u8; 32]) -> Self {
+        Self::new(
+            protection,
+            Target::Create2(Create2::new(ADDRESS, [0; 32], init_code_hash)),
+        )
+    }
+
+    /// Creates a new `deployCreate3` deployment.
+    pub fn create3(protection: Protection) -> Self {
+        Self::new(
+            protection,
+            Target::Create3(Create3::new(
+                ADDRESS,
+                [0; 32],
+                create3::PROXY_INIT_CODE_HASH,
+            )),
+        )
+    }
+
+    fn new(protection: Protection, target: Target) -> Self {
+        let mut createx = Self {
+            protection,
+            salt: [0; 32],
+            target,
+        };
+        createx.update_salt_nonce(|_| ());
+        createx
+    }
+
+    /// Returns the deployment protections.
+    pub fn protection(&self) -> Protection {
+        self.protection
+    }
+
+    /// Returns the entrypoint used for deploying.
+    pub fn entrypoint(&self) -> Entrypoint {
+        match self.target {
+            Target::Create2(_) => Entrypoint::Create2,
+            Target::Create3(_) => Entrypoint::Create3,
+        }
+    }
+
+    /// Returns the salt passed to CreateX.
+    pub fn salt(&self) -> [u8; 32] {
+        self.salt
+    }
+
+    /// Returns the guarded salt that CreateX deploys with.
+    pub fn guarded_salt(&self) -> [u8; 32] {
+        match &self.target {
+            Target::Create2(create2) => create2.salt(),
+            Target::Create3(create3) => create3.salt(),
+        }
+    }
+
+    /// Returns the address of the `CREATE3` proxy, or `None` for `CREATE2`
+    /// deployments.
+    pub fn proxy_address(&self) -> Option<Address> {
+        match &self.target {
+            Target::Create2(_) => None,
+            Target::Create3(create3) => Some(create3.proxy_address()),
+        }
+    }
+
+    /// Returns the deterministic address of the deployed contract.
+    pub fn creation_address(&self) -> Address {
+        match &self.target {
+            Target::Create2(create2) => create2.creation_address(),
+            Target::Create3(create3) => create3.creation_address(),
+        }
+    }
+
+    /// Returns the calldata for deploying the init code with CreateX.
+    pub fn calldata(&self, init_code: &[u8]) -> Vec<u8> {
+        self.entrypoint().calldata(self.salt, init_code)
+    }
+}
+
+impl Deployment for CreateX {
+    fn digest(&self) -> [u8; 32] {
+        let init_code_hash = match &self.target {
+            Target::Create2(create2) => create2.init_code_hash(),
+            Target::Create3(create3) => create3.proxy_init_code_hash(),
+        };
+        keccak::v256_chunked(&[
+            &[self.entrypoint() as u8],
+            &self.protection.prefix(),
+            &abi::uint(self.protection.chain_id.unwrap_or_default()),
+            &init_code_hash,
+        ])
+    }
+
+    fn salt_nonce(&self) -> [u8; 32] {
+        self.salt
+    }
+
+    fn update_salt_nonce(&mut self, f: impl FnOnce(&mut [u8; 32])) {
+        f(&mut self.salt);
+        self.salt[..21].copy_from_slice(&self.protection.prefix());
+        let guarded = self.protection.guard(&self.salt);
+        match &mut self.target {
+            Target::Create2(create2) => create2.set_salt(&guarded),
+            Target::Create3(create3) => create3.set_salt(&guarded),
+        }
+    }
+
+    fn creation_address(&self) -> Address {
+        CreateX::creation_address(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guard_rules() {
+        let sender = address!("5afe5afe5afe5afe5afe5afe5afe5afe5afe5afe");
+        let other = address!("0000000000000000000000000000000000000001");
+        let salt = |deployer: Address, flag: u8| {
+            let mut salt = [0x42; 32];
+            salt[..20].copy_from_slice(&deployer.0);
+            salt[20] = flag;
+            salt
+        };
+
+        // Invalid protection flags revert for the sender and zero address.
+        assert_eq!(guard(salt(sender, 0x02), sender, 1), None);
+        assert_eq!(guard(salt(Address::zero(), 0x02), sender, 1), None);
+
+        // Other deployers hash the salt as is, regardless of the flag.
+        let unprotected = salt(other, 0x02);
+        assert_eq!(
+            guard(unprotected, sender, 1),
+            Some(keccak::v256(&unprotected)),
+        );
+
+        // Cross-chain redeploy protection depends on the chain ID.
+        for deployer in [sender, Address::zero()] {
+            let salt = salt(deployer, 0x01);
+            assert_ne!(guard(salt, sender, 1), guard(salt, sender, 100));
+        }
+    }
+
+    #[test]
+    fn protections_match_guard() {
+        let sender = address!("5afe5afe5afe5afe5afe5afe5afe5afe5afe5afe");
+        for caller in [Some(sender), None] {
+            for chain_id in [Some(1), Some(100), None] {
+                let protection = Protection { caller, chain_id };
+                let salts = protection.salts();
+                for counter in [salts.start(), salts.end() - 1] {
+                    let salt = Nonces::salt_nonce(salts.seed(), counter);
+                    assert_eq!(salt[..21], protection.prefix());
+                    assert_eq!(
+                        guard(salt, sender, chain_id.unwrap_or(1)),
+                        Some(protection.guard(&salt)),
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn deployment_addresses() {
+        let protection = Protection {
+            caller: Some(address!("5afe5afe5afe5afe5afe5afe5afe5afe5afe5afe")),
+            chain_id: Some(1),
+        };
+        let init_code_hash = keccak::v256(b"init code");
+        let mut salt = [0x42; 32];
+        salt[..21].copy_from_slice(&protection.prefix());
+        let guarded = protection.guard(&salt);
+
+        let mut createx = CreateX::create2(protection, init_code_hash);
+        createx.update_salt_nonce(|nonce| *nonce = [0x42; 32]);
+        assert_eq!(createx.salt(), salt);
+        assert_eq!(createx.guarded_salt(), guarded);
+        assert_eq!(
+            createx.creation_address(),
+            Create2::new(ADDRESS, guarded, init_code_hash).creation_address(),
+        );
+
+        let mut createx = CreateX::create3(protection);
+        createx.update_salt_nonce(|nonce| *nonce = [0x42; 32]);
+        let create3 = Create3::new(ADDRESS, guarded, create3::PROXY_INIT_CODE_HASH);
+        assert_eq!(createx.proxy_address(), Some(create3.proxy_address()));
+        assert_eq!(createx.creation_address(), create3.creation_address());
+    }
+
+    #[test]
+    fn calldata() {
+        let salt = hex!("5afe5afe5afe5afe5afe5afe5afe5afe5afe5afe010000000000000000000001");
+        let init_code = hex!("600a600c600039600a6000f3602a60005260206000f3");
+
+        assert_eq!(
+            Entrypoint::Create2.calldata(salt, &init_code),
+            hex!(
+                "26307668
+                 5afe5afe5afe5afe5afe5afe5afe5afe5afe5afe010000000000000000000001
+                 0000000000000000000000000000000000000000000000000000000000000040
+                 0000000000000000000000000000000000000000000000000000000000000016
+                 600a600c600039600a6000f3602a60005260206000f300000000000000000000"
+            ),
+        );
+        assert_eq!(
+            Entrypoint::Create3.calldata(salt, &init_code)[..4],
+            hex!("9c36a286"),
+        );
+    }
+}
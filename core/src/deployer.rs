@@ -0,0 +1,113 @@
+//! Module containing presets for well-known `CREATE2` deployer contracts.
+
+use crate::{address::Address, config::abi, nonce::Nonces};
+use hex_literal::hex;
+
+/// A well-known `CREATE2` deployer contract.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Deployer {
+    /// Arachnid's deterministic deployment proxy, called with the salt
+    /// followed by the init code.
+    ///
+    /// <https://github.com/Arachnid/deterministic-deployment-proxy>
+    DeterministicDeploymentProxy,
+    /// The Safe singleton factory, which is a deployment of the deterministic
+    /// deployment proxy with a dedicated deployer key.
+    ///
+    /// <https://github.com/safe-global/safe-singleton-factory>
+    SafeSingletonFactory,
+    /// 0age's `ImmutableCreate2Factory`, called with `safeCreate2(salt,
+    /// initializationCode)`. The first 20 bytes of the salt must either be
+    /// the caller address or zero.
+    ///
+    /// <https://github.com/0age/metamorphic/blob/master/contracts/ImmutableCreate2Factory.sol>
+    ImmutableCreate2Factory,
+}
+
+impl Deployer {
+    /// Returns the address of the deployer contract.
+    pub fn address(self) -> Address {
+        match self {
+            Self::DeterministicDeploymentProxy => {
+                address!("4e59b44847b379578588920cA78FbF26c0B4956C")
+            }
+            Self::SafeSingletonFactory => address!("914d7Fec6aaC8cd542e72Bca78B30650d45643d7"),
+            Self::ImmutableCreate2Factory => address!("0000000000FFe8B47B3e2130213B802212439497"),
+        }
+    }
+
+    /// Returns the salt nonces that are valid salts for deployments from the
+    /// specified caller, or `None` if any salt is valid.
+    ///
+    /// For deployers that restrict the salt to the caller, deployments
+    /// without a caller use the salts that anyone is allowed to deploy with.
+    pub fn salts(self, caller: Option<Address>) -> Option<Nonces> {
+        match self {
+            Self::DeterministicDeploymentProxy | Self::SafeSingletonFactory => None,
+            Self::ImmutableCreate2Factory => Some(Nonces::prefixed(&caller.unwrap_or_default().0)),
+        }
+    }
+
+    /// Returns the calldata for deploying the init code with the salt.
+    pub fn calldata(self, salt: [u8; 32], init_code: &[u8]) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        match self {
+            Self::DeterministicDeploymentProxy | Self::SafeSingletonFactory => {
+                buffer.extend_from_slice(&salt);
+                buffer.extend_from_slice(init_code);
+            }
+            Self::ImmutableCreate2Factory => {
+                buffer.extend_from_slice(&hex!("64e03087"));
+                buffer.extend_from_slice(&salt);
+                buffer.extend_from_slice(&abi::num(0x40)); // initializationCode.offset
+                buffer.extend_from_slice(&abi::num(init_code.len()));
+                buffer.extend_from_slice(init_code);
+                buffer.extend_from_slice(abi::padding(init_code));
+            }
+        }
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calldata() {
+        let salt = hex!("5afe5afe5afe5afe5afe5afe5afe5afe5afe5afe000000000000000000000001");
+        let init_code = hex!("600a600c600039600a6000f3602a60005260206000f3");
+
+        assert_eq!(
+            Deployer::DeterministicDeploymentProxy.calldata(salt, &init_code),
+            hex!(
+                "5afe5afe5afe5afe5afe5afe5afe5afe5afe5afe000000000000000000000001
+                 600a600c600039600a6000f3602a60005260206000f3"
+            ),
+        );
+        assert_eq!(
+            Deployer::ImmutableCreate2Factory.calldata(salt, &init_code),
+            hex!(
+                "64e03087
+                 5afe5afe5afe5afe5afe5afe5afe5afe5afe5afe000000000000000000000001
+                 0000000000000000000000000000000000000000000000000000000000000040
+                 0000000000000000000000000000000000000000000000000000000000000016
+                 600a600c600039600a6000f3602a60005260206000f300000000000000000000"
+            ),
+        );
+    }
+
+    #[test]
+    fn salt_rules() {
+        assert_eq!(Deployer::DeterministicDeploymentProxy.salts(None), None);
+
+        let caller = address!("5afe5afe5afe5afe5afe5afe5afe5afe5afe5afe");
+        for (caller, prefix) in [(Some(caller), caller), (None, Address::zero())] {
+            let salts = Deployer::ImmutableCreate2Factory.salts(caller).unwrap();
+            for counter in [salts.start(), salts.end() - 1] {
+                let salt = Nonces::salt_nonce(salts.seed(), counter);
+                assert_eq!(salt[..20], prefix.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Module defining deterministic deployments that can be searched for vanity
+//! addresses.
+
+use crate::{address::Address, keccak::simd, pattern::Matcher};
+
+/// A deterministic contract deployment, whose creation address depends on a
+/// 32-byte salt nonce.
+pub trait Deployment: Clone + Send {
+    /// Returns a digest of the deployment parameters other than the salt
+    /// nonce.
+    ///
+    /// Two deployments with the same digest produce the same address for
+    /// every salt nonce.
+    fn digest(&self) -> [u8; 32];
+
+    /// Returns the current salt nonce.
+    fn salt_nonce(&self) -> [u8; 32];
+
+    /// Updates the salt nonce and recomputes the creation address.
+    fn update_salt_nonce(&mut self, f: impl FnOnce(&mut [u8; 32]));
+
+    /// Returns the creation address for the current salt nonce.
+    fn creation_address(&self) -> Address;
+
+    /// Returns whether the creation address matches.
+    fn matches(&self, matcher: &(impl Matcher + ?Sized)) -> bool {
+        matcher.matches(&self.creation_address())
+    }
+
+    /// Returns a multi-lane hasher for computing creation addresses for
+    /// batches of salt nonces, or `None` if the deployment can only be
+    /// computed with the scalar hasher.
+    #[doc(hidden)]
+    fn hasher(&self) -> Option<simd::Hasher> {
+        None
+    }
+}
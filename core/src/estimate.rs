@@ -0,0 +1,72 @@
+//! Module containing search time estimates.
+
+use crate::pattern::Matcher;
+
+/// Estimates for the number of attempts needed to find a matching address.
+///
+/// Each attempt independently matches with a fixed probability, so the number
+/// of attempts until the first match follows a geometric distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Estimate {
+    difficulty: f64,
+}
+
+impl Estimate {
+    /// Creates a new estimate for the specified matcher.
+    pub fn new(matcher: &(impl Matcher + ?Sized)) -> Self {
+        Self {
+            difficulty: matcher.difficulty(),
+        }
+    }
+
+    /// Returns the probability that a single attempt matches.
+    pub fn probability(&self) -> f64 {
+        self.difficulty.recip()
+    }
+
+    /// Returns the expected number of attempts until a match is found.
+    pub fn expected(&self) -> f64 {
+        self.difficulty
+    }
+
+    /// Returns the number of attempts within which a match is found with the
+    /// specified probability, for example `0.5` for the median.
+    pub fn percentile(&self, probability: f64) -> f64 {
+        // Solve `1 - (1 - p)^n = probability` for `n`, using `ln_1p` to stay
+        // accurate for the tiny per-attempt probabilities of long patterns.
+        (-probability).ln_1p() / (-self.probability()).ln_1p()
+    }
+
+    /// Returns the probability of finding a match within the specified number
+    /// of attempts.
+    pub fn probability_within(&self, attempts: f64) -> f64 {
+        -((-self.probability()).ln_1p() * attempts).exp_m1()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::{Checksum, Nibbles, Pattern, Patterns};
+
+    #[test]
+    fn geometric_distribution() {
+        let estimate = Estimate::new(&"5af".parse::<Nibbles>().unwrap());
+        assert_eq!(estimate.expected(), 4096.);
+        assert_eq!(estimate.percentile(0.5).round(), 2839.);
+        assert_eq!(estimate.percentile(0.99).round(), 18860.);
+        assert!((estimate.probability_within(estimate.percentile(0.99)) - 0.99).abs() < 1e-9);
+
+        let estimate = Estimate::new(&Checksum::new("0xDEADbeef".parse::<Pattern>().unwrap()));
+        assert_eq!(estimate.expected(), 16_f64.powi(8) * 256.);
+
+        let estimate = Estimate::new(&Patterns::new(vec![
+            "0x5afe".parse::<Pattern>().unwrap(),
+            "0x..cafe".parse::<Pattern>().unwrap(),
+        ]));
+        assert_eq!(estimate.expected(), 32768.);
+
+        let estimate = Estimate::new(&"0x00000000000000000000".parse::<Pattern>().unwrap());
+        assert!((estimate.percentile(0.5) / (estimate.expected() * 2_f64.ln()) - 1.).abs() < 1e-9);
+    }
+}
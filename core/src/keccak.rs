@@ -1,22 +1,172 @@
-use sha3::{Digest as _, Keccak256};
+pub mod backend;
+pub mod simd;
+
+use self::backend::{Keccak as _, Selected};
+use std::array;
 
 /// Compute the Keccak-256 hash of the input bytes.
 #[inline]
 pub fn v256(bytes: &[u8]) -> [u8; 32] {
-    let mut output = [0u8; 32];
-    let mut hasher = Keccak256::new();
-    hasher.update(bytes);
-    hasher.finalize_into((&mut output).into());
-    output
+    Selected::v256_chunked(&[bytes])
 }
 
 /// Compute the Keccak-256 hash of the input byte chunks.
 pub fn v256_chunked(chunks: &[&[u8]]) -> [u8; 32] {
-    let mut output = [0u8; 32];
-    let mut hasher = Keccak256::new();
-    for chunk in chunks {
-        hasher.update(chunk);
+    Selected::v256_chunked(chunks)
+}
+
+/// The Keccak-256 rate in bytes.
+const RATE: usize = 136;
+
+/// Keccak-256 specialised for the 64-byte Safe salt preimage
+/// `keccak256(initializer) ‖ saltNonce`, or the 96-byte chain-specific
+/// preimage `keccak256(initializer) ‖ saltNonce ‖ chainId`.
+///
+/// The initializer hash, chain ID and padding are laid out once, so hashing a
+/// salt nonce only writes its four lanes before permuting.
+#[derive(Clone, Debug)]
+pub struct SaltHasher {
+    block: [u64; 25],
+}
+
+impl SaltHasher {
+    /// Creates a new hasher for the specified initializer hash.
+    pub fn new(initializer_hash: &[u8; 32]) -> Self {
+        let mut input = [0; 64];
+        input[..32].copy_from_slice(initializer_hash);
+        Self {
+            block: block(&input),
+        }
+    }
+
+    /// Creates a new chain-specific hasher for the specified initializer hash
+    /// and ABI-encoded chain ID.
+    pub fn with_chain_id(initializer_hash: &[u8; 32], chain_id: &[u8; 32]) -> Self {
+        let mut input = [0; 96];
+        input[..32].copy_from_slice(initializer_hash);
+        input[64..].copy_from_slice(chain_id);
+        Self {
+            block: block(&input),
+        }
+    }
+
+    /// Returns the padded block, with zeroed salt nonce lanes.
+    pub(crate) fn block(&self) -> &[u64; 25] {
+        &self.block
+    }
+
+    /// Computes the salt for the specified salt nonce.
+    #[inline]
+    pub fn hash(&self, salt_nonce: &[u8; 32]) -> [u8; 32] {
+        let mut state = self.block;
+        for (lane, bytes) in state[4..8].iter_mut().zip(salt_nonce.chunks_exact(8)) {
+            *lane = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+        Selected::f1600(&mut state);
+        digest(&state)
+    }
+}
+
+/// Keccak-256 specialised for the 85-byte `CREATE2` preimage
+/// `0xff ‖ factory ‖ salt ‖ initCodeHash`.
+///
+/// The salt starts at byte 21, 5 bytes into lane 2, so setting it writes the
+/// upper 3 bytes of lane 2, lanes 3 to 5 and the lower 5 bytes of lane 6. The
+/// remaining lanes, including padding, are laid out once.
+#[derive(Clone, Debug)]
+pub struct Create2Hasher {
+    block: [u64; 25],
+}
+
+impl Create2Hasher {
+    /// The bits of lane 2 that belong to the factory address.
+    const FACTORY_MASK: u64 = 0xff_ffff_ffff;
+
+    /// Creates a new hasher for the specified `CREATE2` preimage.
+    pub fn new(input: &[u8; 85]) -> Self {
+        Self {
+            block: block(input),
+        }
+    }
+
+    /// Returns the padded block, with zeroed salt bytes.
+    pub(crate) fn block(&self) -> [u64; 25] {
+        let mut block = self.block;
+        block[2] &= Self::FACTORY_MASK;
+        block[3..6].fill(0);
+        block[6] &= !Self::FACTORY_MASK;
+        block
+    }
+
+    /// Sets the `CREATE2` salt.
+    #[inline]
+    pub fn set_salt(&mut self, salt: &[u8; 32]) {
+        let salt: [u64; 4] =
+            array::from_fn(|i| u64::from_le_bytes(salt[i * 8..][..8].try_into().unwrap()));
+        let block = &mut self.block;
+        block[2] = (block[2] & Self::FACTORY_MASK) | (salt[0] << 40);
+        block[3] = (salt[0] >> 24) | (salt[1] << 40);
+        block[4] = (salt[1] >> 24) | (salt[2] << 40);
+        block[5] = (salt[2] >> 24) | (salt[3] << 40);
+        block[6] = (block[6] & !Self::FACTORY_MASK) | (salt[3] >> 24);
+    }
+
+    /// Computes the `CREATE2` hash.
+    #[inline]
+    pub fn hash(&self) -> [u8; 32] {
+        let mut state = self.block;
+        Selected::f1600(&mut state);
+        digest(&state)
+    }
+}
+
+/// Lays out a fixed-length input as a single padded Keccak-256 block.
+fn block<const N: usize>(input: &[u8; N]) -> [u64; 25] {
+    const { assert!(N < RATE) };
+    let mut bytes = [0_u8; 200];
+    bytes[..N].copy_from_slice(input);
+    bytes[N] = 0x01;
+    bytes[RATE - 1] |= 0x80;
+    array::from_fn(|i| u64::from_le_bytes(bytes[i * 8..][..8].try_into().unwrap()))
+}
+
+/// Reads the 32-byte Keccak-256 digest from a permuted state.
+#[inline(always)]
+fn digest(state: &[u64; 25]) -> [u8; 32] {
+    let mut digest = [0; 32];
+    for (bytes, lane) in digest.chunks_exact_mut(8).zip(state) {
+        bytes.copy_from_slice(&lane.to_le_bytes());
+    }
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn specialised_hashers_match_generic() {
+        let initializer_hash = v256(b"initializer");
+        let salt = SaltHasher::new(&initializer_hash);
+        let chain_id = [0x0a; 32];
+        let chain_specific = SaltHasher::with_chain_id(&initializer_hash, &chain_id);
+        let mut input = [0xff; 85];
+        input[1..21].fill(0xfa);
+        input[53..].copy_from_slice(&v256(b"init code"));
+        let mut create2 = Create2Hasher::new(&input);
+
+        for seed in 0..16_u8 {
+            let salt_nonce = array::from_fn(|i| seed ^ i as u8);
+            let digest = salt.hash(&salt_nonce);
+            assert_eq!(digest, v256_chunked(&[&initializer_hash, &salt_nonce]));
+            assert_eq!(
+                chain_specific.hash(&salt_nonce),
+                v256_chunked(&[&initializer_hash, &salt_nonce, &chain_id]),
+            );
+
+            create2.set_salt(&digest);
+            input[21..53].copy_from_slice(&digest);
+            assert_eq!(create2.hash(), v256(&input));
+        }
     }
-    hasher.finalize_into((&mut output).into());
-    output
 }
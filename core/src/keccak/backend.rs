@@ -0,0 +1,213 @@
+//! Keccak implementations, selected with cargo features.
+//!
+//! The `sha3`, `tiny-keccak` and `keccak-asm` features each enable a backend.
+//! If several are enabled, for example because of feature unification in a
+//! workspace, `keccak-asm` is preferred over `tiny-keccak`, which is preferred
+//! over `sha3`.
+
+#[cfg(not(any(feature = "sha3", feature = "tiny-keccak", feature = "keccak-asm")))]
+compile_error!("one of the `sha3`, `tiny-keccak` or `keccak-asm` features must be enabled");
+
+/// A Keccak implementation.
+pub trait Keccak {
+    /// Computes the Keccak-256 hash of the input byte chunks.
+    fn v256_chunked(chunks: &[&[u8]]) -> [u8; 32];
+
+    /// Applies the Keccak-f[1600] permutation to the state.
+    fn f1600(state: &mut [u64; 25]);
+}
+
+/// The Keccak backend used by the crate.
+#[cfg(feature = "keccak-asm")]
+pub type Selected = KeccakAsm;
+/// The Keccak backend used by the crate.
+#[cfg(all(feature = "tiny-keccak", not(feature = "keccak-asm")))]
+pub type Selected = TinyKeccak;
+/// The Keccak backend used by the crate.
+#[cfg(all(
+    feature = "sha3",
+    not(any(feature = "tiny-keccak", feature = "keccak-asm"))
+))]
+pub type Selected = Sha3;
+
+/// The RustCrypto `sha3` and `keccak` crates.
+#[cfg(feature = "sha3")]
+pub struct Sha3;
+
+#[cfg(feature = "sha3")]
+impl Keccak for Sha3 {
+    #[inline]
+    fn v256_chunked(chunks: &[&[u8]]) -> [u8; 32] {
+        use sha3::{Digest as _, Keccak256};
+
+        let mut output = [0u8; 32];
+        let mut hasher = Keccak256::new();
+        for chunk in chunks {
+            hasher.update(chunk);
+        }
+        hasher.finalize_into((&mut output).into());
+        output
+    }
+
+    #[inline]
+    fn f1600(state: &mut [u64; 25]) {
+        keccak::f1600(state);
+    }
+}
+
+/// The `tiny-keccak` crate.
+#[cfg(feature = "tiny-keccak")]
+pub struct TinyKeccak;
+
+#[cfg(feature = "tiny-keccak")]
+impl Keccak for TinyKeccak {
+    #[inline]
+    fn v256_chunked(chunks: &[&[u8]]) -> [u8; 32] {
+        use tiny_keccak::Hasher as _;
+
+        let mut output = [0u8; 32];
+        let mut hasher = tiny_keccak::Keccak::v256();
+        for chunk in chunks {
+            hasher.update(chunk);
+        }
+        hasher.finalize(&mut output);
+        output
+    }
+
+    #[inline]
+    fn f1600(state: &mut [u64; 25]) {
+        tiny_keccak::keccakf(state);
+    }
+}
+
+/// The assembly implementations from OpenSSL, through the `keccak-asm` and
+/// `sha3-asm` crates.
+#[cfg(feature = "keccak-asm")]
+pub struct KeccakAsm;
+
+#[cfg(feature = "keccak-asm")]
+impl Keccak for KeccakAsm {
+    #[inline]
+    fn v256_chunked(chunks: &[&[u8]]) -> [u8; 32] {
+        use keccak_asm::{Digest as _, Keccak256};
+
+        let mut output = [0u8; 32];
+        let mut hasher = Keccak256::new();
+        for chunk in chunks {
+            hasher.update(chunk);
+        }
+        hasher.finalize_into((&mut output).into());
+        output
+    }
+
+    #[inline]
+    fn f1600(state: &mut [u64; 25]) {
+        // The permutation is not exposed directly, but absorbing a block of
+        // zeros leaves the state unchanged before permuting it.
+        sha3_asm::sha3_absorb(state, &[0; super::RATE], super::RATE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    /// Keccak-256 digests of `0, 1, 2, ...` byte sequences of various lengths,
+    /// covering empty inputs, partial blocks and inputs on and around the
+    /// 136-byte block boundary.
+    const VECTORS: &[(usize, [u8; 32])] = &[
+        (
+            0,
+            hex!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+        ),
+        (
+            1,
+            hex!("bc36789e7a1e281436464229828f817d6612f7b477d66591ff96a9e064bcc98a"),
+        ),
+        (
+            32,
+            hex!("8ae1aa597fa146ebd3aa2ceddf360668dea5e526567e92b0321816a4e895bd2d"),
+        ),
+        (
+            64,
+            hex!("002030bde3d4cf89919649775cd71875c4d0ab1708a380e03fefc3a28aa24831"),
+        ),
+        (
+            85,
+            hex!("4f867360dd0508bd8707e85e916348c27a53a5a08803515d6cff2d52c1a01854"),
+        ),
+        (
+            135,
+            hex!("cbdfd9dee5faad3818d6b06f95a219fd290b0e1706f6a82e5a595b9ce9faca62"),
+        ),
+        (
+            136,
+            hex!("7ce759f1ab7f9ce437719970c26b0a66ff11fe3e38e17df89cf5d29c7d7f807e"),
+        ),
+        (
+            137,
+            hex!("ac73d4fae68b8453f764007c1a20ce95994187861f0c3227a3a8e99a73a3b1db"),
+        ),
+        (
+            272,
+            hex!("fdf2ec49e749960d3c8521a0219af8d03e30e2b3bf19bd16150ee0eaf133d66e"),
+        ),
+        (
+            300,
+            hex!("a679e749a6af300c36e7ff2255d220864eab27b382f9cfdc5aa4d13563ba36ff"),
+        ),
+    ];
+
+    fn input(len: usize) -> Vec<u8> {
+        (0..len).map(|i| i as u8).collect()
+    }
+
+    fn check<K: Keccak>() {
+        for (len, digest) in VECTORS {
+            let input = input(*len);
+            assert_eq!(K::v256_chunked(&[&input]), *digest, "length {len}");
+
+            let (a, b) = input.split_at(len / 3);
+            assert_eq!(K::v256_chunked(&[a, &[], b]), *digest, "length {len}");
+        }
+
+        // The first lane of Keccak-f[1600] applied to the zero state, from
+        // the Keccak reference test vectors.
+        let mut state = [0; 25];
+        K::f1600(&mut state);
+        assert_eq!(state[0], 0xf1258f7940e1dde7);
+
+        // A single padded block permuted with `f1600` yields the digest.
+        let mut state = [0; 25];
+        state[0] = 0x01;
+        state[16] = 0x80 << 56;
+        K::f1600(&mut state);
+        let mut digest = [0; 32];
+        for (bytes, lane) in digest.chunks_exact_mut(8).zip(state) {
+            bytes.copy_from_slice(&lane.to_le_bytes());
+        }
+        assert_eq!(
+            digest,
+            hex!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+        );
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn sha3() {
+        check::<Sha3>();
+    }
+
+    #[cfg(feature = "tiny-keccak")]
+    #[test]
+    fn tiny_keccak() {
+        check::<TinyKeccak>();
+    }
+
+    #[cfg(feature = "keccak-asm")]
+    #[test]
+    fn keccak_asm() {
+        check::<KeccakAsm>();
+    }
+}
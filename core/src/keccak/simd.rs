@@ -0,0 +1,537 @@
+//! Multi-lane Keccak-256 for computing Safe creation addresses for several
+//! salt nonces at once.
+//!
+//! The Keccak-f[1600] permutation is implemented once, generically over a
+//! vector of 64-bit lanes, where each vector lane holds the state of an
+//! independent hash. The SIMD instruction set is detected at runtime.
+
+use super::{Create2Hasher, SaltHasher};
+use crate::address::Address;
+
+/// The number of salt nonces that are hashed in a single batch.
+pub const BATCH: usize = 8;
+
+/// The available multi-lane Keccak backends.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Backend {
+    /// AVX-512 with 8 lanes per vector.
+    #[cfg(target_arch = "x86_64")]
+    Avx512,
+    /// AVX2 with 4 lanes per vector.
+    #[cfg(target_arch = "x86_64")]
+    Avx2,
+    /// NEON with 2 lanes per vector.
+    #[cfg(target_arch = "aarch64")]
+    Neon,
+    /// Portable lanes, only used for testing the generic permutation.
+    #[cfg(test)]
+    Portable,
+}
+
+impl Backend {
+    /// Detects the fastest multi-lane backend supported by the current CPU,
+    /// returning `None` if the scalar hasher should be used instead.
+    pub fn detect() -> Option<Self> {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx512f") {
+                return Some(Self::Avx512);
+            }
+            if is_x86_feature_detected!("avx2") {
+                return Some(Self::Avx2);
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            return Some(Self::Neon);
+        }
+        #[allow(unreachable_code)]
+        None
+    }
+}
+
+/// Precomputed hash inputs for computing creation addresses of a
+/// deployment.
+#[derive(Clone, Debug)]
+pub struct Hasher {
+    /// The salt block with the salt nonce lanes zeroed out, or `None` if the
+    /// salt nonce is used as the `CREATE2` salt directly.
+    salt: Option<[u64; 25]>,
+    /// The `CREATE2` block with the salt bytes zeroed out.
+    create2: [u64; 25],
+}
+
+impl Hasher {
+    /// Creates a new multi-lane hasher from the pre-laid-out blocks of the
+    /// specialised scalar hashers.
+    pub fn new(salt: &SaltHasher, create2: &Create2Hasher) -> Self {
+        Self {
+            salt: Some(*salt.block()),
+            create2: create2.block(),
+        }
+    }
+
+    /// Creates a new multi-lane hasher that uses the salt nonces as the
+    /// `CREATE2` salts directly.
+    pub fn create2(create2: &Create2Hasher) -> Self {
+        Self {
+            salt: None,
+            create2: create2.block(),
+        }
+    }
+
+    /// Computes the creation addresses for a batch of salt nonces.
+    pub fn addresses(&self, backend: Backend, nonces: &[[u8; 32]; BATCH]) -> [Address; BATCH] {
+        let mut addresses = [Address::zero(); BATCH];
+        match backend {
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx512 => {
+                // SAFETY: the backend is only detected if AVX-512 is supported.
+                unsafe { x86::addresses_avx512(self, nonces, &mut addresses) }
+            }
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx2 => {
+                for (nonces, addresses) in nonces.chunks_exact(4).zip(addresses.chunks_exact_mut(4))
+                {
+                    // SAFETY: the backend is only detected if AVX2 is
+                    // supported.
+                    unsafe {
+                        x86::addresses_avx2(
+                            self,
+                            nonces.try_into().unwrap(),
+                            addresses.try_into().unwrap(),
+                        )
+                    }
+                }
+            }
+            #[cfg(target_arch = "aarch64")]
+            Backend::Neon => {
+                for (nonces, addresses) in nonces.chunks_exact(2).zip(addresses.chunks_exact_mut(2))
+                {
+                    self.addresses_lanes::<aarch64::Neon, 2>(
+                        nonces.try_into().unwrap(),
+                        addresses.try_into().unwrap(),
+                    );
+                }
+            }
+            #[cfg(test)]
+            Backend::Portable => {
+                self.addresses_lanes::<[u64; BATCH], BATCH>(nonces, &mut addresses)
+            }
+        }
+        addresses
+    }
+
+    /// Computes the creation addresses for `N` salt nonces at once.
+    #[inline(always)]
+    fn addresses_lanes<L: Lanes<N>, const N: usize>(
+        &self,
+        nonces: &[[u8; 32]; N],
+        addresses: &mut [Address; N],
+    ) {
+        let nonces: [L; 4] = std::array::from_fn(|i| {
+            L::from_array(
+                nonces.map(|nonce| u64::from_le_bytes(nonce[i * 8..][..8].try_into().unwrap())),
+            )
+        });
+
+        // Salt: `keccak256(initializerHash ‖ saltNonce)`, where the salt nonce
+        // occupies lanes 4 to 7.
+        let salt = match &self.salt {
+            Some(block) => {
+                let mut state = block.map(L::splat);
+                state[4..8].copy_from_slice(&nonces);
+                keccak_f(&mut state);
+                [state[0], state[1], state[2], state[3]]
+            }
+            None => nonces,
+        };
+
+        // `CREATE2`: `keccak256(0xff ‖ factory ‖ salt ‖ initCodeHash)`, where
+        // the salt starts at byte 21, 5 bytes into lane 2.
+        let mut state = self.create2.map(L::splat);
+        state[2] = state[2].or(salt[0].shl(40));
+        state[3] = salt[0].shr(24).or(salt[1].shl(40));
+        state[4] = salt[1].shr(24).or(salt[2].shl(40));
+        state[5] = salt[2].shr(24).or(salt[3].shl(40));
+        state[6] = state[6].or(salt[3].shr(24));
+        keccak_f(&mut state);
+
+        // The address is the last 20 bytes of the digest: the upper half of
+        // lane 1 and all of lanes 2 and 3.
+        let (a, b, c) = (
+            state[1].to_array(),
+            state[2].to_array(),
+            state[3].to_array(),
+        );
+        for (i, address) in addresses.iter_mut().enumerate() {
+            address.0[0..4].copy_from_slice(&a[i].to_le_bytes()[4..]);
+            address.0[4..12].copy_from_slice(&b[i].to_le_bytes());
+            address.0[12..20].copy_from_slice(&c[i].to_le_bytes());
+        }
+    }
+}
+
+/// A vector of `N` independent 64-bit Keccak lanes.
+trait Lanes<const N: usize>: Copy {
+    fn splat(value: u64) -> Self;
+    fn from_array(values: [u64; N]) -> Self;
+    fn to_array(self) -> [u64; N];
+    fn xor(self, other: Self) -> Self;
+    fn or(self, other: Self) -> Self;
+    /// Computes `!self & other`.
+    fn andnot(self, other: Self) -> Self;
+    fn shl(self, n: u32) -> Self;
+    fn shr(self, n: u32) -> Self;
+
+    #[inline(always)]
+    fn rotl(self, n: u32) -> Self {
+        self.shl(n).or(self.shr(64 - n))
+    }
+}
+
+/// The Keccak-f[1600] round constants.
+const RC: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// The rotation offsets for the combined ρ and π steps.
+const RHO: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+/// The lane permutation for the combined ρ and π steps.
+const PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// The Keccak-f[1600] permutation, where lane `(x, y)` is at index `x + 5y`.
+#[inline(always)]
+fn keccak_f<L: Lanes<N>, const N: usize>(a: &mut [L; 25]) {
+    for rc in RC {
+        // θ
+        let mut c = [L::splat(0); 5];
+        for x in 0..5 {
+            c[x] = a[x]
+                .xor(a[x + 5])
+                .xor(a[x + 10])
+                .xor(a[x + 15])
+                .xor(a[x + 20]);
+        }
+        for x in 0..5 {
+            let d = c[(x + 4) % 5].xor(c[(x + 1) % 5].rotl(1));
+            for y in 0..5 {
+                a[5 * y + x] = a[5 * y + x].xor(d);
+            }
+        }
+
+        // ρ and π
+        let mut last = a[1];
+        for (&pi, &rho) in PI.iter().zip(&RHO) {
+            let next = a[pi];
+            a[pi] = last.rotl(rho);
+            last = next;
+        }
+
+        // χ
+        for y in 0..5 {
+            let row = [
+                a[5 * y],
+                a[5 * y + 1],
+                a[5 * y + 2],
+                a[5 * y + 3],
+                a[5 * y + 4],
+            ];
+            for x in 0..5 {
+                a[5 * y + x] = row[x].xor(row[(x + 1) % 5].andnot(row[(x + 2) % 5]));
+            }
+        }
+
+        // ι
+        a[0] = a[0].xor(L::splat(rc));
+    }
+}
+
+#[cfg(test)]
+impl<const N: usize> Lanes<N> for [u64; N] {
+    fn splat(value: u64) -> Self {
+        [value; N]
+    }
+
+    fn from_array(values: [u64; N]) -> Self {
+        values
+    }
+
+    fn to_array(self) -> [u64; N] {
+        self
+    }
+
+    fn xor(self, other: Self) -> Self {
+        std::array::from_fn(|i| self[i] ^ other[i])
+    }
+
+    fn or(self, other: Self) -> Self {
+        std::array::from_fn(|i| self[i] | other[i])
+    }
+
+    fn andnot(self, other: Self) -> Self {
+        std::array::from_fn(|i| !self[i] & other[i])
+    }
+
+    fn shl(self, n: u32) -> Self {
+        self.map(|lane| lane << n)
+    }
+
+    fn shr(self, n: u32) -> Self {
+        self.map(|lane| lane >> n)
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use super::{Hasher, Lanes};
+    use crate::address::Address;
+    use std::arch::x86_64::*;
+
+    #[derive(Clone, Copy)]
+    pub struct Avx2(__m256i);
+
+    impl Lanes<4> for Avx2 {
+        #[inline(always)]
+        fn splat(value: u64) -> Self {
+            Self(unsafe { _mm256_set1_epi64x(value as _) })
+        }
+
+        #[inline(always)]
+        fn from_array(values: [u64; 4]) -> Self {
+            Self(unsafe { _mm256_loadu_si256(values.as_ptr().cast()) })
+        }
+
+        #[inline(always)]
+        fn to_array(self) -> [u64; 4] {
+            let mut values = [0; 4];
+            unsafe { _mm256_storeu_si256(values.as_mut_ptr().cast(), self.0) };
+            values
+        }
+
+        #[inline(always)]
+        fn xor(self, other: Self) -> Self {
+            Self(unsafe { _mm256_xor_si256(self.0, other.0) })
+        }
+
+        #[inline(always)]
+        fn or(self, other: Self) -> Self {
+            Self(unsafe { _mm256_or_si256(self.0, other.0) })
+        }
+
+        #[inline(always)]
+        fn andnot(self, other: Self) -> Self {
+            Self(unsafe { _mm256_andnot_si256(self.0, other.0) })
+        }
+
+        #[inline(always)]
+        fn shl(self, n: u32) -> Self {
+            Self(unsafe { _mm256_sllv_epi64(self.0, _mm256_set1_epi64x(n as _)) })
+        }
+
+        #[inline(always)]
+        fn shr(self, n: u32) -> Self {
+            Self(unsafe { _mm256_srlv_epi64(self.0, _mm256_set1_epi64x(n as _)) })
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    pub struct Avx512(__m512i);
+
+    impl Lanes<8> for Avx512 {
+        #[inline(always)]
+        fn splat(value: u64) -> Self {
+            Self(unsafe { _mm512_set1_epi64(value as _) })
+        }
+
+        #[inline(always)]
+        fn from_array(values: [u64; 8]) -> Self {
+            Self(unsafe { _mm512_loadu_si512(values.as_ptr().cast()) })
+        }
+
+        #[inline(always)]
+        fn to_array(self) -> [u64; 8] {
+            let mut values = [0; 8];
+            unsafe { _mm512_storeu_si512(values.as_mut_ptr().cast(), self.0) };
+            values
+        }
+
+        #[inline(always)]
+        fn xor(self, other: Self) -> Self {
+            Self(unsafe { _mm512_xor_si512(self.0, other.0) })
+        }
+
+        #[inline(always)]
+        fn or(self, other: Self) -> Self {
+            Self(unsafe { _mm512_or_si512(self.0, other.0) })
+        }
+
+        #[inline(always)]
+        fn andnot(self, other: Self) -> Self {
+            Self(unsafe { _mm512_andnot_si512(self.0, other.0) })
+        }
+
+        #[inline(always)]
+        fn shl(self, n: u32) -> Self {
+            Self(unsafe { _mm512_sllv_epi64(self.0, _mm512_set1_epi64(n as _)) })
+        }
+
+        #[inline(always)]
+        fn shr(self, n: u32) -> Self {
+            Self(unsafe { _mm512_srlv_epi64(self.0, _mm512_set1_epi64(n as _)) })
+        }
+
+        #[inline(always)]
+        fn rotl(self, n: u32) -> Self {
+            Self(unsafe { _mm512_rolv_epi64(self.0, _mm512_set1_epi64(n as _)) })
+        }
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub fn addresses_avx2(hasher: &Hasher, nonces: &[[u8; 32]; 4], addresses: &mut [Address; 4]) {
+        hasher.addresses_lanes::<Avx2, 4>(nonces, addresses)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    pub fn addresses_avx512(hasher: &Hasher, nonces: &[[u8; 32]; 8], addresses: &mut [Address; 8]) {
+        hasher.addresses_lanes::<Avx512, 8>(nonces, addresses)
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+#[allow(unused_unsafe)]
+mod aarch64 {
+    use super::Lanes;
+    use std::arch::aarch64::*;
+
+    #[derive(Clone, Copy)]
+    pub struct Neon(uint64x2_t);
+
+    impl Lanes<2> for Neon {
+        #[inline(always)]
+        fn splat(value: u64) -> Self {
+            Self(unsafe { vdupq_n_u64(value) })
+        }
+
+        #[inline(always)]
+        fn from_array(values: [u64; 2]) -> Self {
+            Self(unsafe { vld1q_u64(values.as_ptr()) })
+        }
+
+        #[inline(always)]
+        fn to_array(self) -> [u64; 2] {
+            let mut values = [0; 2];
+            unsafe { vst1q_u64(values.as_mut_ptr(), self.0) };
+            values
+        }
+
+        #[inline(always)]
+        fn xor(self, other: Self) -> Self {
+            Self(unsafe { veorq_u64(self.0, other.0) })
+        }
+
+        #[inline(always)]
+        fn or(self, other: Self) -> Self {
+            Self(unsafe { vorrq_u64(self.0, other.0) })
+        }
+
+        #[inline(always)]
+        fn andnot(self, other: Self) -> Self {
+            // `vbicq_u64(a, b)` computes `a & !b`.
+            Self(unsafe { vbicq_u64(other.0, self.0) })
+        }
+
+        #[inline(always)]
+        fn shl(self, n: u32) -> Self {
+            Self(unsafe { vshlq_u64(self.0, vdupq_n_s64(n as i64)) })
+        }
+
+        #[inline(always)]
+        fn shr(self, n: u32) -> Self {
+            // Negative shift amounts shift to the right.
+            Self(unsafe { vshlq_u64(self.0, vdupq_n_s64(-(n as i64))) })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create2::Create2, keccak};
+
+    fn scalar(salt: &[u8; 32], create2: &Create2, nonce: &[u8; 32]) -> Address {
+        let mut create2 = create2.clone();
+        create2.set_salt(&keccak::v256(&[&salt[..], &nonce[..]].concat()));
+        create2.creation_address()
+    }
+
+    fn check(backend: Backend) {
+        let salt = [0x5a; 32];
+        let create2 = Create2::new(
+            address!("5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f"),
+            [0; 32],
+            [0xc0; 32],
+        );
+        let hasher = Hasher::new(&SaltHasher::new(&salt), create2.hasher());
+        let create2_hasher = Hasher::create2(create2.hasher());
+
+        for seed in 0..16_u8 {
+            let nonces: [[u8; 32]; BATCH] =
+                std::array::from_fn(|i| std::array::from_fn(|j| seed ^ (i * 32 + j) as u8));
+            assert_eq!(
+                hasher.addresses(backend, &nonces),
+                nonces.map(|nonce| scalar(&salt, &create2, &nonce)),
+                "{backend:?}",
+            );
+            assert_eq!(
+                create2_hasher.addresses(backend, &nonces),
+                nonces.map(|nonce| {
+                    let mut create2 = create2.clone();
+                    create2.set_salt(&nonce);
+                    create2.creation_address()
+                }),
+                "{backend:?}",
+            );
+        }
+    }
+
+    #[test]
+    fn portable_matches_scalar() {
+        check(Backend::Portable);
+    }
+
+    #[test]
+    fn detected_matches_scalar() {
+        if let Some(backend) = Backend::detect() {
+            check(backend);
+        }
+    }
+}
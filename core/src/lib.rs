@@ -1,29 +1,460 @@
 #[macro_use]
 mod address;
+mod cancel;
 pub mod config;
 mod create2;
+pub mod create3;
+pub mod createx;
+pub mod deployer;
+mod deployment;
+mod estimate;
 mod keccak;
+mod nonce;
+pub mod pattern;
 mod safe;
+pub mod score;
+mod stats;
 
 pub use self::{
     address::{Address, NonZeroAddress},
+    cancel::CancellationToken,
     config::Configuration,
+    create2::Create2,
+    create3::Create3,
+    deployment::Deployment,
+    estimate::Estimate,
+    nonce::Nonces,
+    pattern::{Checksum, Matcher, Nibbles, Pattern, PatternError, Patterns},
     safe::{Safe, Transaction},
+    stats::{Attempts, Stats},
 };
 pub use hex_literal::hex;
+use keccak::simd::{Backend, BATCH};
+#[doc(hidden)]
+pub use keccak::{backend as keccak_backend, Create2Hasher, SaltHasher};
 use rand::{rngs::SmallRng, Rng as _, SeedableRng as _};
+use std::{
+    array,
+    num::NonZeroUsize,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::Instant,
+};
+
+/// The outcome of a search that can be stopped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    /// A matching deployment was found with the salt nonce for the counter.
+    Found(u128),
+    /// The search was cancelled.
+    Cancelled,
+    /// The search deadline passed.
+    TimedOut,
+    /// All salt nonces in the range were searched without finding a match.
+    Exhausted,
+}
 
-/// Search for a vanity address with the specified Safe parameters and prefix.
-pub fn search(safe: &mut Safe, prefix: &[u8]) {
+/// Search for a vanity address with the specified deployment and address
+/// matcher.
+pub fn search(deployment: &mut impl Deployment, matcher: &(impl Matcher + ?Sized)) {
     let mut rng = SmallRng::from_os_rng();
-    while !search_iter(safe, prefix, |n| rng.fill(n)) {}
+    while !search_iter(deployment, matcher, |n| rng.fill(n)) {}
+}
+
+/// Search for a vanity address with the specified deployment and address
+/// matcher by walking a range of deterministic salt nonces.
+///
+/// Returns the counter of the matching salt nonce, or `None` if the range was
+/// exhausted. The range is advanced past all checked salt nonces, so that the
+/// search can be continued from where it left off, and the number of checked
+/// salt nonces is added to the attempt counter.
+pub fn search_range(
+    deployment: &mut impl Deployment,
+    matcher: &(impl Matcher + ?Sized),
+    nonces: &mut Nonces,
+    attempts: &Attempts,
+) -> Option<u128> {
+    let (start, end) = (nonces.start(), nonces.end());
+    let mut counter = start;
+    let mut found = None;
+
+    // Search batches of salt nonces with SIMD if the CPU supports it, and any
+    // remaining ones with the scalar hasher.
+    if let (Some(backend), Some(hasher)) = (Backend::detect(), deployment.hasher()) {
+        while found.is_none() && end - counter >= BATCH as u128 {
+            let batch = array::from_fn(|i| Nonces::salt_nonce(nonces.seed(), counter + i as u128));
+            found = hasher
+                .addresses(backend, &batch)
+                .iter()
+                .position(|address| matcher.matches(address))
+                .map(|i| counter + i as u128);
+            counter += BATCH as u128;
+        }
+    }
+    if let Some(counter) = found {
+        deployment.update_salt_nonce(|n| *n = Nonces::salt_nonce(nonces.seed(), counter));
+    } else {
+        deployment.update_salt_nonce(|n| *n = Nonces::salt_nonce(nonces.seed(), counter));
+        for counter in counter..end {
+            if search_iter(deployment, matcher, |n| {
+                n[16..].copy_from_slice(&counter.to_be_bytes())
+            }) {
+                found = Some(counter);
+                break;
+            }
+        }
+    }
+
+    let next = found.map_or(end, |counter| counter + 1);
+    *nonces = nonces.starting_at(next);
+    attempts.add((next - start).try_into().unwrap_or(u64::MAX));
+    found
+}
+
+/// Search for a vanity address with the specified deployment and address
+/// matcher by walking a range of deterministic salt nonces until a match is
+/// found, the search is cancelled or the optional deadline passes.
+///
+/// The cancellation token and deadline are checked periodically, so the
+/// search may run slightly past either of them. On success, the deployment
+/// is updated with the matching salt nonce. In all cases, the range is
+/// advanced past all checked salt nonces.
+pub fn search_until(
+    deployment: &mut impl Deployment,
+    matcher: &(impl Matcher + ?Sized),
+    nonces: &mut Nonces,
+    cancellation: &CancellationToken,
+    deadline: Option<Instant>,
+) -> (Outcome, Stats) {
+    search_while(deployment, matcher, nonces, deadline, || {
+        cancellation.is_cancelled()
+    })
+}
+
+/// Search a range of deterministic salt nonces until a match is found, the
+/// `cancelled` function returns `true` or the optional deadline passes.
+fn search_while(
+    deployment: &mut impl Deployment,
+    matcher: &(impl Matcher + ?Sized),
+    nonces: &mut Nonces,
+    deadline: Option<Instant>,
+    cancelled: impl Fn() -> bool,
+) -> (Outcome, Stats) {
+    // Checking for cancellation requires reading shared state and the clock,
+    // so only do it every so often in order to not slow down the search.
+    const CHECK_INTERVAL: u128 = 0x1000;
+
+    let attempts = Attempts::new();
+    let start = Instant::now();
+    let outcome = loop {
+        if cancelled() {
+            break Outcome::Cancelled;
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            break Outcome::TimedOut;
+        }
+        if nonces.is_empty() {
+            break Outcome::Exhausted;
+        }
+
+        let mut chunk = nonces.take(CHECK_INTERVAL);
+        let found = search_range(deployment, matcher, &mut chunk, &attempts);
+        *nonces = nonces.starting_at(chunk.start());
+        if let Some(counter) = found {
+            break Outcome::Found(counter);
+        }
+    };
+
+    let stats = Stats {
+        attempts: attempts.get(),
+        elapsed: start.elapsed(),
+    };
+    (outcome, stats)
+}
+
+/// The result of a parallel search.
+#[derive(Clone)]
+pub struct Parallel<D = Safe> {
+    /// The outcome of the search.
+    pub outcome: Outcome,
+    /// The matching deployment, if one was found.
+    pub deployment: Option<D>,
+    /// The statistics for each of the search threads.
+    pub stats: Vec<Stats>,
+}
+
+/// Search for a vanity address in parallel, using one thread for each of the
+/// specified salt nonce ranges, until a match is found, the search is
+/// cancelled or the optional deadline passes.
+///
+/// As soon as one thread finds a match, the other threads are stopped through
+/// a shared flag and all threads are joined before returning. The ranges are
+/// advanced past all checked salt nonces, except for matches that lost the
+/// race to be returned, so that continuing the search with the same ranges
+/// finds them again.
+pub fn search_parallel<D: Deployment>(
+    deployment: &D,
+    matcher: &(impl Matcher + Sync + ?Sized),
+    ranges: &mut [Nonces],
+    cancellation: &CancellationToken,
+    deadline: Option<Instant>,
+) -> Parallel<D> {
+    let stop = AtomicBool::new(false);
+    let results = thread::scope(|scope| {
+        let workers = ranges
+            .iter_mut()
+            .map(|nonces| {
+                let stop = &stop;
+                let mut deployment = deployment.clone();
+                scope.spawn(move || {
+                    let (outcome, stats) =
+                        search_while(&mut deployment, matcher, nonces, deadline, || {
+                            stop.load(Ordering::Relaxed) || cancellation.is_cancelled()
+                        });
+                    match outcome {
+                        Outcome::Found(counter) if stop.swap(true, Ordering::Relaxed) => {
+                            *nonces = nonces.starting_at(counter);
+                            (Outcome::Cancelled, None, stats)
+                        }
+                        Outcome::Found(_) => (outcome, Some(deployment), stats),
+                        _ => (outcome, None, stats),
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("search thread panicked"))
+            .collect::<Vec<_>>()
+    });
+
+    let outcomes = results.iter().map(|(outcome, _, _)| *outcome);
+    let outcome = outcomes
+        .clone()
+        .find(|outcome| matches!(outcome, Outcome::Found(_)))
+        .or_else(|| {
+            outcomes
+                .clone()
+                .find(|outcome| *outcome == Outcome::Cancelled)
+        })
+        .or_else(|| {
+            outcomes
+                .clone()
+                .find(|outcome| *outcome == Outcome::TimedOut)
+        })
+        .unwrap_or(Outcome::Exhausted);
+    let (deployment, stats) = results.into_iter().fold(
+        (None, Vec::new()),
+        |(found, mut stats), (_, deployment, s)| {
+            stats.push(s);
+            (found.or(deployment), stats)
+        },
+    );
+    Parallel {
+        outcome,
+        deployment,
+        stats,
+    }
+}
+
+/// Search for a vanity address in parallel on the specified number of
+/// threads, each walking an equal shard of the salt nonce range, until a match
+/// is found, the search is cancelled or the optional deadline passes.
+///
+/// This is a convenience wrapper around [`search_parallel`] for searches that
+/// do not need to be continued from where they left off.
+pub fn search_threads<D: Deployment>(
+    deployment: &D,
+    matcher: &(impl Matcher + Sync + ?Sized),
+    nonces: Nonces,
+    threads: NonZeroUsize,
+    cancellation: &CancellationToken,
+    deadline: Option<Instant>,
+) -> Parallel<D> {
+    let threads = threads.get();
+    let mut ranges = (0..threads)
+        .map(|i| nonces.shard(i, threads))
+        .collect::<Vec<_>>();
+    search_parallel(deployment, matcher, &mut ranges, cancellation, deadline)
+}
+
+/// Run a batch of iterations of the vanity address search, using the SIMD
+/// multi-lane hasher if the CPU supports it, returning the index of the first
+/// matching salt nonce.
+///
+/// This function is publically exposed to facilitate benchmarking.
+#[doc(hidden)]
+pub fn search_batch(
+    deployment: &mut impl Deployment,
+    matcher: &(impl Matcher + ?Sized),
+    nonces: &[[u8; 32]; BATCH],
+) -> Option<usize> {
+    let found = match (Backend::detect(), deployment.hasher()) {
+        (Some(backend), Some(hasher)) => hasher
+            .addresses(backend, nonces)
+            .iter()
+            .position(|address| matcher.matches(address)),
+        _ => nonces
+            .iter()
+            .position(|nonce| search_iter(deployment, matcher, |n| *n = *nonce)),
+    };
+    if let Some(i) = found {
+        deployment.update_salt_nonce(|n| *n = nonces[i]);
+    }
+    found
 }
 
 /// Run a single iteration of the vanity address search.
 ///
 /// This function is publically exposed to facilitate benchmarking.
 #[doc(hidden)]
-pub fn search_iter(safe: &mut Safe, prefix: &[u8], update: impl FnOnce(&mut [u8; 32])) -> bool {
-    safe.update_salt_nonce(update);
-    safe.creation_address().0.starts_with(prefix)
+pub fn search_iter(
+    deployment: &mut impl Deployment,
+    matcher: &(impl Matcher + ?Sized),
+    update: impl FnOnce(&mut [u8; 32]),
+) -> bool {
+    deployment.update_salt_nonce(update);
+    deployment.matches(matcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::safe::tests::safe;
+    use std::time::Duration;
+
+    #[test]
+    fn search_range_is_deterministic() {
+        let mut safe = safe();
+        let pattern = "0x5a".parse::<Pattern>().unwrap();
+
+        let attempts = Attempts::new();
+
+        let mut nonces = Nonces::new(42);
+        let first = search_range(&mut safe, &pattern, &mut nonces, &attempts).unwrap();
+        let second = search_range(&mut safe, &pattern, &mut nonces, &attempts).unwrap();
+        assert!(first < second);
+        assert_eq!(nonces.start(), second + 1);
+        assert_eq!(safe.salt_nonce(), Nonces::salt_nonce(42, second));
+        assert_eq!(attempts.get(), second as u64 + 1);
+
+        let mut nonces = Nonces::new(42);
+        assert_eq!(
+            search_range(&mut safe, &pattern, &mut nonces, &attempts),
+            Some(first),
+        );
+
+        let mut nonces = Nonces::range(42, first + 1, second);
+        assert_eq!(
+            search_range(&mut safe, &pattern, &mut nonces, &attempts),
+            None,
+        );
+        assert!(nonces.is_empty());
+    }
+
+    #[test]
+    fn search_until_stops() {
+        let mut safe = safe();
+        let pattern = "0x5a".parse::<Pattern>().unwrap();
+        let never = "0x5afe5afe5afe5afe5afe".parse::<Pattern>().unwrap();
+        let cancellation = CancellationToken::new();
+
+        let mut nonces = Nonces::new(42);
+        let expected = search_range(&mut safe, &pattern, &mut nonces, &Attempts::new());
+
+        let mut nonces = Nonces::new(42);
+        let (outcome, stats) = search_until(&mut safe, &pattern, &mut nonces, &cancellation, None);
+        assert_eq!(Some(outcome), expected.map(Outcome::Found));
+        assert_eq!(u128::from(stats.attempts), nonces.start());
+
+        let mut nonces = Nonces::range(42, 0, 100);
+        let (outcome, stats) = search_until(&mut safe, &never, &mut nonces, &cancellation, None);
+        assert_eq!(outcome, Outcome::Exhausted);
+        assert_eq!(stats.attempts, 100);
+
+        let mut nonces = Nonces::new(42);
+        let (outcome, _) = search_until(
+            &mut safe,
+            &never,
+            &mut nonces,
+            &cancellation,
+            Some(Instant::now() + Duration::from_millis(10)),
+        );
+        assert_eq!(outcome, Outcome::TimedOut);
+
+        cancellation.cancel();
+        let mut nonces = Nonces::new(42);
+        let (outcome, stats) = search_until(&mut safe, &never, &mut nonces, &cancellation, None);
+        assert_eq!(outcome, Outcome::Cancelled);
+        assert_eq!(stats.attempts, 0);
+    }
+
+    #[test]
+    fn search_parallel_finds_all_matches() {
+        let pattern = "0x5".parse::<Pattern>().unwrap();
+        let nonces = Nonces::range(42, 0, 1000);
+
+        let mut expected = Vec::new();
+        let (mut safe, mut remaining) = (safe(), nonces);
+        while let Some(counter) =
+            search_range(&mut safe, &pattern, &mut remaining, &Attempts::new())
+        {
+            expected.push(counter);
+        }
+
+        let mut found = Vec::new();
+        let mut ranges = (0..4).map(|i| nonces.shard(i, 4)).collect::<Vec<_>>();
+        let cancellation = CancellationToken::new();
+        loop {
+            let result = search_parallel(&safe, &pattern, &mut ranges, &cancellation, None);
+            assert_eq!(result.stats.len(), 4);
+            match result.outcome {
+                Outcome::Found(counter) => {
+                    let safe = result.deployment.unwrap();
+                    assert!(safe.matches(&pattern));
+                    assert_eq!(safe.salt_nonce(), Nonces::salt_nonce(42, counter));
+                    found.push(counter);
+                }
+                Outcome::Exhausted => break,
+                outcome => panic!("unexpected outcome {outcome:?}"),
+            }
+        }
+        found.sort();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn search_threads_shards_range() {
+        let pattern = "0x5".parse::<Pattern>().unwrap();
+        let cancellation = CancellationToken::new();
+
+        let result = search_threads(
+            &safe(),
+            &pattern,
+            Nonces::range(42, 0, 1000),
+            NonZeroUsize::new(4).unwrap(),
+            &cancellation,
+            None,
+        );
+        assert_eq!(result.stats.len(), 4);
+        assert!(matches!(result.outcome, Outcome::Found(_)));
+        assert!(result.deployment.unwrap().matches(&pattern));
+
+        let never = "0x0000000000000000000000000000000000000000"
+            .parse::<Pattern>()
+            .unwrap();
+        let result = search_threads(
+            &safe(),
+            &never,
+            Nonces::range(42, 0, 1000),
+            NonZeroUsize::new(3).unwrap(),
+            &cancellation,
+            None,
+        );
+        assert_eq!(result.outcome, Outcome::Exhausted);
+        assert_eq!(
+            result.stats.iter().map(|stats| stats.attempts).sum::<u64>(),
+            1000,
+        );
+    }
 }
@@ -0,0 +1,179 @@
+//! Module containing deterministic salt nonce ranges.
+
+/// A range of deterministic salt nonces.
+///
+/// Salt nonces are 256-bit integers, where the upper 128 bits are a fixed seed
+/// and the lower 128 bits are a counter. This makes searches reproducible and
+/// allows splitting the nonce space into disjoint parts that are walked by
+/// incrementing the counter.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Nonces {
+    seed: u128,
+    start: u128,
+    end: u128,
+}
+
+impl Nonces {
+    /// Creates a new range covering all counters for the specified seed.
+    pub fn new(seed: u128) -> Self {
+        Self::range(seed, 0, u128::MAX)
+    }
+
+    /// Creates a new range of counters `start..end` for the specified seed.
+    pub fn range(seed: u128, start: u128, end: u128) -> Self {
+        Self {
+            seed,
+            start,
+            end: end.max(start),
+        }
+    }
+
+    /// Creates a new range covering all salt nonces that start with the
+    /// specified prefix.
+    ///
+    /// The first 16 bytes of the prefix make up the seed and the remaining
+    /// bytes the upper bits of the counter, so for example a 20-byte prefix
+    /// leaves 96 bits to search.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the prefix is shorter than 16 or longer than 31 bytes.
+    pub fn prefixed(prefix: &[u8]) -> Self {
+        assert!(
+            (16..32).contains(&prefix.len()),
+            "invalid salt nonce prefix length {}",
+            prefix.len(),
+        );
+
+        let mut salt_nonce = [0; 32];
+        salt_nonce[..prefix.len()].copy_from_slice(prefix);
+        let (seed, start) = Self::split(salt_nonce);
+        let last = start | (u128::MAX >> ((prefix.len() - 16) * 8));
+        Self::range(seed, start, last.saturating_add(1))
+    }
+
+    /// Creates a new range covering all counters for a random seed.
+    pub fn random() -> Self {
+        Self::new(rand::random())
+    }
+
+    /// Returns the seed for the salt nonces.
+    pub fn seed(&self) -> u128 {
+        self.seed
+    }
+
+    /// Returns the next counter in the range.
+    pub fn start(&self) -> u128 {
+        self.start
+    }
+
+    /// Returns the exclusive end counter of the range.
+    pub fn end(&self) -> u128 {
+        self.end
+    }
+
+    /// Returns whether the range has been exhausted.
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// Returns the range starting at the specified counter.
+    pub fn starting_at(self, start: u128) -> Self {
+        Self::range(self.seed, start, self.end)
+    }
+
+    /// Returns the range of at most `count` counters at the start of this
+    /// range.
+    pub fn take(self, count: u128) -> Self {
+        Self::range(
+            self.seed,
+            self.start,
+            self.start.saturating_add(count).min(self.end),
+        )
+    }
+
+    /// Splits the range into `count` contiguous and disjoint shards and returns
+    /// the shard at the specified index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is not less than the number of shards.
+    pub fn shard(self, index: usize, count: usize) -> Self {
+        assert!(index < count, "invalid shard {index}/{count}");
+
+        let size = (self.end - self.start) / count as u128;
+        let start = self.start + size * index as u128;
+        let end = if index + 1 == count {
+            self.end
+        } else {
+            start + size
+        };
+        Self::range(self.seed, start, end)
+    }
+
+    /// Returns the salt nonce for the specified seed and counter.
+    pub fn salt_nonce(seed: u128, counter: u128) -> [u8; 32] {
+        let mut salt_nonce = [0_u8; 32];
+        salt_nonce[..16].copy_from_slice(&seed.to_be_bytes());
+        salt_nonce[16..].copy_from_slice(&counter.to_be_bytes());
+        salt_nonce
+    }
+
+    /// Splits a salt nonce into its seed and counter.
+    pub fn split(salt_nonce: [u8; 32]) -> (u128, u128) {
+        (
+            u128::from_be_bytes(salt_nonce[..16].try_into().unwrap()),
+            u128::from_be_bytes(salt_nonce[16..].try_into().unwrap()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn salt_nonce_layout() {
+        let salt_nonce = Nonces::salt_nonce(0x5afe, 42);
+        assert_eq!(
+            salt_nonce,
+            hex!("00000000000000000000000000005afe0000000000000000000000000000002a"),
+        );
+        assert_eq!(Nonces::split(salt_nonce), (0x5afe, 42));
+    }
+
+    #[test]
+    fn disjoint_shards() {
+        let nonces = Nonces::range(1, 10, 110);
+        let shards = (0..3).map(|i| nonces.shard(i, 3)).collect::<Vec<_>>();
+        assert_eq!(
+            shards,
+            [
+                Nonces::range(1, 10, 43),
+                Nonces::range(1, 43, 76),
+                Nonces::range(1, 76, 110),
+            ],
+        );
+        assert_eq!(shards[1].shard(1, 2), Nonces::range(1, 59, 76));
+        assert_eq!(shards[2].take(10), Nonces::range(1, 76, 86));
+        assert_eq!(shards[2].take(100), shards[2]);
+    }
+
+    #[test]
+    fn prefixed_range() {
+        let nonces = Nonces::prefixed(&hex!("5afe5afe5afe5afe5afe5afe5afe5afe5afe5afe"));
+        assert_eq!(
+            Nonces::salt_nonce(nonces.seed(), nonces.start()),
+            hex!("5afe5afe5afe5afe5afe5afe5afe5afe5afe5afe000000000000000000000000"),
+        );
+        assert_eq!(
+            Nonces::salt_nonce(nonces.seed(), nonces.end() - 1),
+            hex!("5afe5afe5afe5afe5afe5afe5afe5afe5afe5afeffffffffffffffffffffffff"),
+        );
+
+        let nonces = Nonces::prefixed(&[0; 21]);
+        assert_eq!((nonces.start(), nonces.end()), (0, 1 << 88));
+        assert_eq!(Nonces::prefixed(&[0; 16]), Nonces::new(0));
+    }
+}
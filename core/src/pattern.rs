@@ -0,0 +1,676 @@
+//! Module containing address patterns used for vanity address searches.
+
+use crate::address::Address;
+use hex::FromHexError;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::{self, FromStr},
+};
+
+/// A matcher for vanity addresses.
+pub trait Matcher {
+    /// Returns whether or not the specified address matches.
+    fn matches(&self, address: &Address) -> bool;
+
+    /// Returns the expected number of random addresses that need to be tried
+    /// before finding one that matches.
+    fn difficulty(&self) -> f64;
+}
+
+/// A masked address pattern.
+///
+/// The pattern is stored as a 20-byte value along with a mask of the nibbles
+/// that are significant, so that any nibble of the address may be a wildcard.
+/// Matching an address is done with three masked word comparisons.
+///
+/// The letter case of the pattern is recorded, but is only taken into account
+/// when matching with a [`Checksum`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Pattern {
+    value: [u8; 20],
+    mask: [u8; 20],
+    upper: [u8; 20],
+}
+
+impl Pattern {
+    /// Creates a new pattern matching addresses that both start with the
+    /// specified prefix and end with the specified suffix.
+    pub fn new(prefix: Nibbles, suffix: Nibbles) -> Result<Self, PatternError> {
+        Self::prefix(prefix)?.merge(&Self::suffix(suffix)?)
+    }
+
+    /// Creates a new pattern matching addresses with the specified prefix.
+    pub fn prefix(prefix: Nibbles) -> Result<Self, PatternError> {
+        let len = prefix.value.len();
+        if len > 20 {
+            return Err(PatternError::TooLong);
+        }
+
+        let mut pattern = Self::default();
+        pattern.value[..len].copy_from_slice(&prefix.value);
+        pattern.mask[..len].copy_from_slice(&prefix.mask);
+        pattern.upper[..len].copy_from_slice(&prefix.upper);
+        Ok(pattern)
+    }
+
+    /// Creates a new pattern matching addresses with the specified suffix.
+    pub fn suffix(suffix: Nibbles) -> Result<Self, PatternError> {
+        let suffix = suffix.right_aligned();
+        let start = 20_usize
+            .checked_sub(suffix.value.len())
+            .ok_or(PatternError::TooLong)?;
+
+        let mut pattern = Self::default();
+        pattern.value[start..].copy_from_slice(&suffix.value);
+        pattern.mask[start..].copy_from_slice(&suffix.mask);
+        pattern.upper[start..].copy_from_slice(&suffix.upper);
+        Ok(pattern)
+    }
+
+    /// Merges two patterns into one that matches addresses matching both.
+    pub fn merge(&self, other: &Self) -> Result<Self, PatternError> {
+        let mut merged = Self::default();
+        for i in 0..20 {
+            let diff = (self.value[i] ^ other.value[i]) | (self.upper[i] ^ other.upper[i]);
+            if diff & self.mask[i] & other.mask[i] != 0 {
+                return Err(PatternError::Conflict);
+            }
+            merged.value[i] = self.value[i] | other.value[i];
+            merged.mask[i] = self.mask[i] | other.mask[i];
+            merged.upper[i] = self.upper[i] | other.upper[i];
+        }
+        Ok(merged)
+    }
+
+    /// Returns the number of significant nibbles in the pattern.
+    pub fn len(&self) -> usize {
+        self.mask.iter().map(|m| m.count_ones() as usize / 4).sum()
+    }
+
+    /// Returns whether the pattern is empty and matches everything.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Matcher for Pattern {
+    #[inline]
+    fn matches(&self, address: &Address) -> bool {
+        #[inline(always)]
+        fn words(bytes: &[u8; 20]) -> (u64, u64, u32) {
+            (
+                u64::from_ne_bytes(bytes[0..8].try_into().unwrap()),
+                u64::from_ne_bytes(bytes[8..16].try_into().unwrap()),
+                u32::from_ne_bytes(bytes[16..20].try_into().unwrap()),
+            )
+        }
+
+        let (a0, a1, a2) = words(&address.0);
+        let (v0, v1, v2) = words(&self.value);
+        let (m0, m1, m2) = words(&self.mask);
+        ((a0 ^ v0) & m0) | ((a1 ^ v1) & m1) | ((a2 ^ v2) & m2) as u64 == 0
+    }
+
+    fn difficulty(&self) -> f64 {
+        16_f64.powi(self.len() as _)
+    }
+}
+
+impl AsRef<Pattern> for Pattern {
+    fn as_ref(&self) -> &Pattern {
+        self
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+        let mut buf = *b"0x????????????????????????????????????????";
+        for (i, c) in buf[2..].iter_mut().enumerate() {
+            let shift = if i % 2 == 0 { 4 } else { 0 };
+            if (self.mask[i / 2] >> shift) & 0xf != 0 {
+                *c = DIGITS[((self.value[i / 2] >> shift) & 0xf) as usize];
+            }
+            if (self.upper[i / 2] >> shift) & 0xf != 0 {
+                c.make_ascii_uppercase();
+            }
+        }
+
+        f.write_str(unsafe { str::from_utf8_unchecked(&buf) })
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = PatternError;
+
+    /// Parses a pattern where `?` characters are nibble wildcards and an
+    /// optional `..` expands to as many wildcards as are needed to fill the
+    /// entire address. Patterns shorter than an address without a `..` are
+    /// treated as prefixes.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix("0x").unwrap_or(s);
+        let (prefix, suffix) = s.split_once("..").unwrap_or((s, ""));
+        let (prefix_len, suffix_len) = (prefix.chars().count(), suffix.chars().count());
+        if prefix_len + suffix_len > 40 {
+            return Err(PatternError::TooLong);
+        }
+
+        let mut pattern = Self::default();
+        let nibbles = prefix.chars().enumerate().map(|(i, c)| (i, i, c)).chain(
+            suffix
+                .chars()
+                .enumerate()
+                .map(|(i, c)| (40 - suffix_len + i, prefix_len + 2 + i, c)),
+        );
+        for (position, index, c) in nibbles {
+            if c == '?' {
+                continue;
+            }
+            let nibble = c
+                .to_digit(16)
+                .ok_or(PatternError::InvalidCharacter { c, index })? as u8;
+            let shift = if position % 2 == 0 { 4 } else { 0 };
+            pattern.value[position / 2] |= nibble << shift;
+            pattern.mask[position / 2] |= 0xf << shift;
+            if c.is_ascii_uppercase() {
+                pattern.upper[position / 2] |= 0xf << shift;
+            }
+        }
+
+        Ok(pattern)
+    }
+}
+
+/// An error creating an address pattern.
+#[derive(Debug, Eq, PartialEq)]
+pub enum PatternError {
+    /// The pattern contains an invalid character.
+    InvalidCharacter { c: char, index: usize },
+    /// The pattern is longer than an address.
+    TooLong,
+    /// The pattern has conflicting nibbles.
+    Conflict,
+}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter { c, index } => {
+                write!(f, "invalid character {c:?} at position {index}")
+            }
+            Self::TooLong => f.write_str("pattern is longer than an address"),
+            Self::Conflict => f.write_str("pattern has conflicting nibbles"),
+        }
+    }
+}
+
+impl Error for PatternError {}
+
+/// A masked address pattern that also matches the letter case of the
+/// [EIP-55](https://eips.ethereum.org/EIPS/eip-55) checksummed address.
+///
+/// The checksum requires an additional Keccak-256 hash, so it is only computed
+/// for addresses that already match the pattern ignoring case.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checksum {
+    pattern: Pattern,
+    mask: [u8; 20],
+    value: [u8; 20],
+}
+
+impl Checksum {
+    /// Creates a new checksum matcher for the specified pattern.
+    pub fn new(pattern: Pattern) -> Self {
+        // In EIP-55, a letter is upper case if the corresponding nibble of
+        // the checksum digest has its high bit set.
+        let mut mask = [0_u8; 20];
+        let mut value = [0_u8; 20];
+        for i in 0..40 {
+            let shift = if i % 2 == 0 { 4 } else { 0 };
+            let nibble = (pattern.value[i / 2] >> shift) & 0xf;
+            if (pattern.mask[i / 2] >> shift) & 0xf == 0 || nibble < 0xa {
+                continue;
+            }
+            mask[i / 2] |= 0x8 << shift;
+            if (pattern.upper[i / 2] >> shift) & 0xf != 0 {
+                value[i / 2] |= 0x8 << shift;
+            }
+        }
+
+        Self {
+            pattern,
+            mask,
+            value,
+        }
+    }
+
+    /// Returns the number of letters whose case needs to match.
+    pub fn letters(&self) -> usize {
+        self.mask.iter().map(|m| m.count_ones() as usize).sum()
+    }
+}
+
+impl AsRef<Pattern> for Checksum {
+    fn as_ref(&self) -> &Pattern {
+        &self.pattern
+    }
+}
+
+impl Matcher for Checksum {
+    #[inline]
+    fn matches(&self, address: &Address) -> bool {
+        self.pattern.matches(address) && {
+            let digest = address.checksum_digest();
+            digest[..20]
+                .iter()
+                .zip(&self.mask)
+                .zip(&self.value)
+                .all(|((d, m), v)| d & m == *v)
+        }
+    }
+
+    /// Returns the expected number of attempts, where each letter whose case
+    /// needs to match doubles the difficulty of the pattern.
+    fn difficulty(&self) -> f64 {
+        self.pattern.difficulty() * 2_f64.powi(self.letters() as _)
+    }
+}
+
+/// A set of address patterns that are matched in a single pass.
+///
+/// Patterns are indexed in a lookup table by the first byte of the address,
+/// so that for most addresses only a single table lookup is needed regardless
+/// of the number of patterns in the set.
+#[derive(Clone, Debug)]
+pub struct Patterns<M = Pattern> {
+    patterns: Vec<M>,
+    table: Vec<Vec<usize>>,
+}
+
+impl<M> Patterns<M>
+where
+    M: AsRef<Pattern> + Matcher,
+{
+    /// Creates a new set of patterns.
+    pub fn new(patterns: Vec<M>) -> Self {
+        let table = (0..=u8::MAX)
+            .map(|byte| {
+                patterns
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, pattern)| {
+                        let pattern = pattern.as_ref();
+                        byte & pattern.mask[0] == pattern.value[0]
+                    })
+                    .map(|(i, _)| i)
+                    .collect()
+            })
+            .collect();
+        Self { patterns, table }
+    }
+
+    /// Returns the index of the first pattern in the set that matches the
+    /// specified address.
+    #[inline]
+    pub fn find(&self, address: &Address) -> Option<usize> {
+        self.table[address.0[0] as usize]
+            .iter()
+            .copied()
+            .find(|&i| self.patterns[i].matches(address))
+    }
+
+    /// Returns the indices of all patterns in the set that match the
+    /// specified address, in ascending order.
+    pub fn find_all<'a>(&'a self, address: &'a Address) -> impl Iterator<Item = usize> + 'a {
+        self.table[address.0[0] as usize]
+            .iter()
+            .copied()
+            .filter(move |&i| self.patterns[i].matches(address))
+    }
+
+    /// Returns the patterns in the set.
+    pub fn patterns(&self) -> &[M] {
+        &self.patterns
+    }
+}
+
+impl<M> Matcher for Patterns<M>
+where
+    M: AsRef<Pattern> + Matcher,
+{
+    #[inline]
+    fn matches(&self, address: &Address) -> bool {
+        self.find(address).is_some()
+    }
+
+    fn difficulty(&self) -> f64 {
+        self.patterns
+            .iter()
+            .map(|pattern| pattern.difficulty().recip())
+            .sum::<f64>()
+            .recip()
+    }
+}
+
+/// A nibble-granular byte pattern.
+///
+/// The pattern is stored as a byte value along with a mask of the nibbles that
+/// are significant, which allows matching an odd number of hex characters.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Nibbles {
+    value: Vec<u8>,
+    mask: Vec<u8>,
+    upper: Vec<u8>,
+}
+
+impl Nibbles {
+    /// Creates a new pattern matching all nibbles of the specified bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            value: bytes.to_vec(),
+            mask: vec![0xff; bytes.len()],
+            upper: vec![0; bytes.len()],
+        }
+    }
+
+    /// Returns the number of significant nibbles in the pattern.
+    pub fn len(&self) -> usize {
+        self.mask.iter().map(|m| m.count_ones() as usize / 4).sum()
+    }
+
+    /// Returns whether the pattern is empty and matches everything.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the pattern with an odd number of nibbles shifted to the right
+    /// by one nibble, so that it ends on a byte boundary.
+    fn right_aligned(self) -> Self {
+        if self.mask.last().is_none_or(|m| m & 0x0f != 0) {
+            return self;
+        }
+
+        let shr = |bytes: &[u8]| {
+            let mut carry = 0;
+            bytes
+                .iter()
+                .map(|b| {
+                    let shifted = (carry << 4) | (b >> 4);
+                    carry = b & 0x0f;
+                    shifted
+                })
+                .collect()
+        };
+        Self {
+            value: shr(&self.value),
+            mask: shr(&self.mask),
+            upper: shr(&self.upper),
+        }
+    }
+
+    /// Returns whether the pattern matches the specified bytes, which must
+    /// have the same length as the pattern.
+    #[inline]
+    fn matches_bytes(&self, bytes: &[u8]) -> bool {
+        bytes
+            .iter()
+            .zip(&self.mask)
+            .zip(&self.value)
+            .all(|((b, m), v)| b & m == *v)
+    }
+}
+
+impl Matcher for Nibbles {
+    #[inline]
+    fn matches(&self, address: &Address) -> bool {
+        address
+            .0
+            .get(..self.value.len())
+            .is_some_and(|bytes| self.matches_bytes(bytes))
+    }
+
+    fn difficulty(&self) -> f64 {
+        16_f64.powi(self.len() as _)
+    }
+}
+
+impl FromStr for Nibbles {
+    type Err = FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix("0x").unwrap_or(s);
+
+        let mut nibbles = Self {
+            value: vec![0; s.len().div_ceil(2)],
+            mask: vec![0; s.len().div_ceil(2)],
+            upper: vec![0; s.len().div_ceil(2)],
+        };
+        for (index, c) in s.chars().enumerate() {
+            let nibble =
+                c.to_digit(16)
+                    .ok_or(FromHexError::InvalidHexCharacter { c, index })? as u8;
+            let shift = if index % 2 == 0 { 4 } else { 0 };
+            nibbles.value[index / 2] |= nibble << shift;
+            nibbles.mask[index / 2] |= 0xf << shift;
+            if c.is_ascii_uppercase() {
+                nibbles.upper[index / 2] |= 0xf << shift;
+            }
+        }
+
+        Ok(nibbles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nibbles(s: &str) -> Nibbles {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_odd_nibbles() {
+        assert_eq!(
+            nibbles("0x5af"),
+            Nibbles {
+                value: vec![0x5a, 0xf0],
+                mask: vec![0xff, 0xf0],
+                upper: vec![0x00, 0x00],
+            },
+        );
+        assert_eq!(nibbles("0x5af").len(), 3);
+        assert_eq!(nibbles("5afe"), Nibbles::from_bytes(&[0x5a, 0xfe]));
+        assert!(nibbles("0x").is_empty());
+        assert!("0x5afg".parse::<Nibbles>().is_err());
+    }
+
+    #[test]
+    fn matches_prefix_and_suffix() {
+        let address = address!("5afe00000000000000000000000000000005afe0");
+        for (pattern, matches) in [
+            (Pattern::prefix(nibbles("5afe")), true),
+            (Pattern::prefix(nibbles("5af")), true),
+            (Pattern::prefix(nibbles("5afe0")), true),
+            (Pattern::prefix(nibbles("5afe1")), false),
+            (Pattern::suffix(nibbles("5afe0")), true),
+            (Pattern::suffix(nibbles("05afe0")), true),
+            (Pattern::suffix(nibbles("fe0")), true),
+            (Pattern::suffix(nibbles("5afe")), false),
+            (Pattern::new(nibbles("5af"), nibbles("afe0")), true),
+            (Pattern::new(nibbles("5af"), nibbles("beef")), false),
+            (Pattern::new(nibbles("dead"), nibbles("5afe0")), false),
+            (Ok(Pattern::default()), true),
+        ] {
+            assert_eq!(pattern.unwrap().matches(&address), matches);
+        }
+    }
+
+    #[test]
+    fn invalid_prefix_and_suffix() {
+        assert_eq!(
+            Pattern::prefix(Nibbles::from_bytes(&[0; 21])),
+            Err(PatternError::TooLong),
+        );
+        assert_eq!(
+            Pattern::suffix(Nibbles::from_bytes(&[0; 21])),
+            Err(PatternError::TooLong),
+        );
+        assert_eq!(
+            Pattern::new(nibbles(&"0".repeat(39)), nibbles("ff")),
+            Err(PatternError::Conflict),
+        );
+        assert!(Pattern::new(nibbles(&"0".repeat(39)), nibbles("0f")).is_ok());
+        assert_eq!(
+            Pattern::new(nibbles("dead"), nibbles(&format!("De{}", "0".repeat(36)))),
+            Err(PatternError::Conflict),
+        );
+    }
+
+    #[test]
+    fn parses_masked_pattern() {
+        for (s, display) in [
+            (
+                "0xdead????beef",
+                "0xdead????beef????????????????????????????",
+            ),
+            ("0x00..00", "0x00????????????????????????????????????00"),
+            ("5af..e", "0x5af????????????????????????????????????e"),
+            ("0x..", "0x????????????????????????????????????????"),
+            (
+                "0x0123456789abcdef0123456789ABCDEF01234567",
+                "0x0123456789abcdef0123456789ABCDEF01234567",
+            ),
+        ] {
+            assert_eq!(s.parse::<Pattern>().unwrap().to_string(), display);
+        }
+        assert_eq!(
+            "0xdead..beef".parse::<Pattern>().unwrap(),
+            Pattern::new(nibbles("dead"), nibbles("beef")).unwrap(),
+        );
+    }
+
+    #[test]
+    fn pattern_difficulty() {
+        assert_eq!(nibbles("0x5af").difficulty(), 4096.);
+        assert_eq!(
+            "0xdead..be?f".parse::<Pattern>().unwrap().difficulty(),
+            16_f64.powi(7),
+        );
+
+        let checksum = Checksum::new("0xDEADbeef".parse().unwrap());
+        assert_eq!(checksum.letters(), 8);
+        assert_eq!(checksum.difficulty(), 16_f64.powi(8) * 256.);
+        assert_eq!(
+            Checksum::new("0x0123..4567".parse().unwrap()).difficulty(),
+            16_f64.powi(8),
+        );
+    }
+
+    #[test]
+    fn matches_checksum_case() {
+        let address = address!("90F8bf6A479f320ead074411a4B0e7944Ea8c9C1");
+        for (pattern, matches) in [
+            ("0x90F8bf6A", true),
+            ("0x90f8bf6a", false),
+            ("0x90F8BF6A", false),
+            ("0x90F8..4Ea8c9C1", true),
+            ("0x90F8..4Ea8C9C1", false),
+            ("0x90F8??6A", true),
+            ("0x9", true),
+        ] {
+            let pattern = pattern.parse::<Pattern>().unwrap();
+            assert!(pattern.matches(&address));
+            assert_eq!(Checksum::new(pattern).matches(&address), matches);
+        }
+    }
+
+    #[test]
+    fn finds_matching_pattern_in_set() {
+        let patterns = Patterns::new(
+            ["0x5afe", "0xcafe", "0x?afe", "0xbeef", "0x..f00d"]
+                .into_iter()
+                .map(|pattern| pattern.parse::<Pattern>().unwrap())
+                .collect(),
+        );
+        for (address, index) in [
+            (
+                address!("5afe000000000000000000000000000000000000"),
+                Some(0),
+            ),
+            (
+                address!("cafe000000000000000000000000000000000000"),
+                Some(1),
+            ),
+            (
+                address!("0afe000000000000000000000000000000000000"),
+                Some(2),
+            ),
+            (
+                address!("beef000000000000000000000000000000000000"),
+                Some(3),
+            ),
+            (
+                address!("000000000000000000000000000000000000f00d"),
+                Some(4),
+            ),
+            (
+                address!("5afe00000000000000000000000000000000f00d"),
+                Some(0),
+            ),
+            (address!("dead000000000000000000000000000000000000"), None),
+        ] {
+            assert_eq!(patterns.find(&address), index);
+            assert_eq!(patterns.matches(&address), index.is_some());
+        }
+        assert_eq!(
+            patterns
+                .find_all(&address!("5afe00000000000000000000000000000000f00d"))
+                .collect::<Vec<_>>(),
+            [0, 2, 4],
+        );
+        assert_eq!(
+            patterns.difficulty(),
+            (4. / 16_f64.powi(4) + 1. / 16_f64.powi(3)).recip(),
+        );
+    }
+
+    #[test]
+    fn invalid_masked_pattern() {
+        assert_eq!(
+            "0xdeag".parse::<Pattern>(),
+            Err(PatternError::InvalidCharacter { c: 'g', index: 3 }),
+        );
+        assert_eq!(
+            "0x00..0.0".parse::<Pattern>(),
+            Err(PatternError::InvalidCharacter { c: '.', index: 5 }),
+        );
+        assert_eq!(
+            format!("0x{}", "0".repeat(41)).parse::<Pattern>(),
+            Err(PatternError::TooLong),
+        );
+    }
+
+    #[test]
+    fn matches_masked_pattern() {
+        let pattern = "0xdead????beef..0?0".parse::<Pattern>().unwrap();
+        for (address, matches) in [
+            (address!("dead0000beef00000000000000000000000000f0"), true),
+            (address!("deadffffbeeffffffffffffffffffffffffff0f0"), true),
+            (address!("dead0000beef0000000000000000000000000f10"), false),
+            (address!("dead0000beee00000000000000000000000000f0"), false),
+            (address!("ceadffffbeefffffffffffffffffffffffff0f0f"), false),
+        ] {
+            assert_eq!(pattern.matches(&address), matches);
+        }
+    }
+
+    #[test]
+    fn odd_nibble_prefix() {
+        assert!(nibbles("0x5af").matches(&address!("5af0000000000000000000000000000000000000")));
+        assert!(nibbles("0x5af").matches(&address!("5afe000000000000000000000000000000000000")));
+        assert!(!nibbles("0x5af").matches(&address!("5a0f000000000000000000000000000000000000")));
+    }
+}
@@ -1,13 +1,22 @@
 //! Module containing Safe
 
-use crate::{address::Address, create2::Create2, keccak, Configuration};
+use crate::{
+    address::Address,
+    config,
+    create2::Create2,
+    deployment::Deployment,
+    keccak::{self, simd},
+    pattern::Matcher,
+    Configuration,
+};
 
 /// Safe deployment for computing deterministic addresses.
 #[derive(Clone)]
 pub struct Safe {
     config: Configuration,
     initializer: Vec<u8>,
-    salt: [u8; 64],
+    salt_nonce: [u8; 32],
+    salt: keccak::SaltHasher,
     create2: Create2,
 }
 
@@ -25,19 +34,24 @@ impl Safe {
     pub fn new(config: Configuration) -> Self {
         let initializer = config.account.initializer();
 
-        let mut salt = [0_u8; 64];
-        salt[0..32].copy_from_slice(&keccak::v256(&initializer));
-
-        let mut create2 = Create2::new(
+        let salt_nonce = [0_u8; 32];
+        let initializer_hash = keccak::v256(&initializer);
+        let salt = match config.proxy.chain_specific {
+            None => keccak::SaltHasher::new(&initializer_hash),
+            Some(chain_id) => {
+                keccak::SaltHasher::with_chain_id(&initializer_hash, &config::abi::uint(chain_id))
+            }
+        };
+        let create2 = Create2::new(
             config.proxy.factory.get(),
-            Default::default(),
+            salt.hash(&salt_nonce),
             config.proxy.init_code_hash(),
         );
-        *create2.salt_mut() = keccak::v256(&salt);
 
         Self {
             config,
             initializer,
+            salt_nonce,
             salt,
             create2,
         }
@@ -48,14 +62,14 @@ impl Safe {
         self.create2.creation_address()
     }
 
-    /// Returns whether the Safe's address starts with the specified prefix.
-    pub fn matches(&self, prefix: &[u8]) -> bool {
-        self.creation_address().0.starts_with(prefix)
+    /// Returns whether the Safe's address matches.
+    pub fn matches(&self, matcher: &(impl Matcher + ?Sized)) -> bool {
+        matcher.matches(&self.creation_address())
     }
 
     /// Returns the current salt nonce value for the Safe deployment.
     pub fn salt_nonce(&self) -> [u8; 32] {
-        self.salt[32..64].try_into().unwrap()
+        self.salt_nonce
     }
 
     /// Returns the initializer calldata for the Safe.
@@ -65,29 +79,80 @@ impl Safe {
 
     /// Updates the salt nonce and recomputes the `CREATE2` salt.
     pub fn update_salt_nonce(&mut self, f: impl FnOnce(&mut [u8; 32])) {
-        let salt_nonce = unsafe { &mut *self.salt.get_unchecked_mut(32..).as_mut_ptr().cast() };
-        f(salt_nonce);
-        *self.create2.salt_mut() = keccak::v256(&self.salt);
+        f(&mut self.salt_nonce);
+        self.create2.set_salt(&self.salt.hash(&self.salt_nonce));
     }
 
     /// Returns the transaction information for the current safe deployment.
     pub fn transaction(&self) -> Transaction {
         Transaction {
             to: self.config.proxy.factory.get(),
-            calldata: self
-                .config
-                .proxy
-                .create_proxy_with_nonce(&self.initializer, self.salt_nonce()),
+            calldata: match self.config.proxy.chain_specific {
+                None => self
+                    .config
+                    .proxy
+                    .create_proxy_with_nonce(&self.initializer, self.salt_nonce()),
+                Some(_) => self
+                    .config
+                    .proxy
+                    .create_chain_specific_proxy_with_nonce(&self.initializer, self.salt_nonce()),
+            },
         }
     }
 }
 
+impl Deployment for Safe {
+    fn digest(&self) -> [u8; 32] {
+        self.config.digest()
+    }
+
+    fn salt_nonce(&self) -> [u8; 32] {
+        Safe::salt_nonce(self)
+    }
+
+    fn update_salt_nonce(&mut self, f: impl FnOnce(&mut [u8; 32])) {
+        Safe::update_salt_nonce(self, f)
+    }
+
+    fn creation_address(&self) -> Address {
+        Safe::creation_address(self)
+    }
+
+    fn hasher(&self) -> Option<simd::Hasher> {
+        Some(simd::Hasher::new(&self.salt, self.create2.hasher()))
+    }
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use crate::config;
     use hex_literal::hex;
 
+    /// Returns a 1-of-1 Safe configuration for tests.
+    pub(crate) fn config() -> Configuration {
+        Configuration {
+            proxy: config::Proxy {
+                factory: address!(nz "1111111111111111111111111111111111111111"),
+                init_code: vec![],
+                singleton: address!(nz "2222222222222222222222222222222222222222"),
+                chain_specific: None,
+            },
+            account: config::Account {
+                owners: vec![address!(nz "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")],
+                threshold: 1,
+                setup: None,
+                fallback_handler: None,
+                identifier: None,
+            },
+        }
+    }
+
+    /// Returns a Safe with the [`config`] configuration for tests.
+    pub(crate) fn safe() -> Safe {
+        Safe::new(config())
+    }
+
     #[test]
     fn transaction() {
         let mut safe = Safe::new(Configuration {
@@ -95,6 +160,7 @@ mod tests {
                 factory: address!(nz "1111111111111111111111111111111111111111"),
                 init_code: vec![],
                 singleton: address!(nz "2222222222222222222222222222222222222222"),
+                chain_specific: None,
             },
             account: config::Account {
                 owners: vec![
@@ -139,4 +205,34 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn chain_specific_salt() {
+        let mut config = config();
+        config.proxy.chain_specific = Some(100);
+        let mut safe = Safe::new(config.clone());
+        safe.update_salt_nonce(|nonce| nonce.fill(0xee));
+
+        let salt = keccak::v256_chunked(&[
+            &keccak::v256(safe.initializer()),
+            &[0xee; 32],
+            &config::abi::uint(100),
+        ]);
+        assert_eq!(
+            safe.creation_address(),
+            Create2::new(
+                config.proxy.factory.get(),
+                salt,
+                config.proxy.init_code_hash(),
+            )
+            .creation_address(),
+        );
+        assert_eq!(safe.transaction().calldata[..4], hex!("ec9e80bb"));
+        assert_eq!(
+            safe.transaction().calldata[4..],
+            config
+                .proxy
+                .create_proxy_with_nonce(safe.initializer(), [0xee; 32])[4..],
+        );
+    }
 }
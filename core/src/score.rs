@@ -0,0 +1,375 @@
+//! Module containing address scoring for open-ended vanity address searches.
+
+use crate::{
+    address::Address, cancel::CancellationToken, nonce::Nonces, pattern::Matcher, safe::Safe,
+    stats::Attempts, Outcome,
+};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Instant,
+};
+
+/// A scorer for vanity addresses, where higher scores are better.
+pub trait Scorer {
+    /// Returns the score for the specified address.
+    fn score(&self, address: &Address) -> u32;
+}
+
+/// Scores addresses by their number of leading zero nibbles.
+///
+/// Leading zero bytes make addresses cheaper to use in calldata.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LeadingZeros;
+
+impl Scorer for LeadingZeros {
+    #[inline]
+    fn score(&self, address: &Address) -> u32 {
+        let hi = u128::from_be_bytes(address.0[0..16].try_into().unwrap());
+        let lo = u32::from_be_bytes(address.0[16..20].try_into().unwrap());
+        let zeros = if hi != 0 {
+            hi.leading_zeros()
+        } else {
+            128 + lo.leading_zeros()
+        };
+        zeros / 4
+    }
+}
+
+/// Scores addresses by their total number of zero bytes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ZeroBytes;
+
+impl Scorer for ZeroBytes {
+    #[inline]
+    fn score(&self, address: &Address) -> u32 {
+        address.0.iter().filter(|b| **b == 0).count() as _
+    }
+}
+
+/// Scores addresses by their longest run of a single repeated nibble.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RepeatedNibbles;
+
+impl Scorer for RepeatedNibbles {
+    #[inline]
+    fn score(&self, address: &Address) -> u32 {
+        let (mut longest, mut run, mut last) = (0, 0, None);
+        for nibble in address.0.iter().flat_map(|b| [b >> 4, b & 0xf]) {
+            run = if last == Some(nibble) { run + 1 } else { 1 };
+            last = Some(nibble);
+            longest = longest.max(run);
+        }
+        longest
+    }
+}
+
+/// The best scoring Safe found so far, shared across search threads.
+#[derive(Default)]
+pub struct Best {
+    score: AtomicU64,
+    safe: Mutex<Option<(u32, Safe)>>,
+}
+
+impl Best {
+    /// Creates a new best-so-far tracker without any Safe.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the best score so far.
+    pub fn score(&self) -> Option<u32> {
+        // The score is stored offset by one so that 0 means no Safe was found.
+        self.score
+            .load(Ordering::Relaxed)
+            .checked_sub(1)
+            .map(|s| s as _)
+    }
+
+    /// Returns the best scoring Safe so far along with its score.
+    pub fn get(&self) -> Option<(u32, Safe)> {
+        self.safe.lock().unwrap().clone()
+    }
+
+    /// Offers a new Safe with the specified score, returning `true` if it is
+    /// strictly better than the best so far.
+    pub fn offer(&self, score: u32, safe: &Safe) -> bool {
+        if self.score().is_some_and(|best| score <= best) {
+            return false;
+        }
+
+        let mut best = self.safe.lock().unwrap();
+        if best.as_ref().is_some_and(|(best, _)| score <= *best) {
+            return false;
+        }
+        *best = Some((score, safe.clone()));
+        self.score.store(score as u64 + 1, Ordering::Relaxed);
+        true
+    }
+}
+
+/// Limits for stopping a scoring search.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Limits {
+    /// Stop once a Safe with at least this score has been found.
+    pub min_score: Option<u32>,
+    /// Stop once this deadline has passed.
+    pub deadline: Option<Instant>,
+}
+
+impl Limits {
+    /// Returns whether the search should stop with the current best.
+    fn reached(&self, best: &Best) -> bool {
+        matches!((self.min_score, best.score()), (Some(min), Some(score)) if score >= min)
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+/// A matcher for addresses that score strictly better than the best so far,
+/// which turns a scoring search into a regular vanity address search.
+struct Improves<'a, S: ?Sized> {
+    scorer: &'a S,
+    best: &'a Best,
+}
+
+impl<S> Matcher for Improves<'_, S>
+where
+    S: Scorer + ?Sized,
+{
+    #[inline]
+    fn matches(&self, address: &Address) -> bool {
+        self.best
+            .score()
+            .is_none_or(|best| self.scorer.score(address) > best)
+    }
+
+    fn difficulty(&self) -> f64 {
+        // The difficulty changes with every improvement, and is not needed
+        // for searching.
+        1.
+    }
+}
+
+/// Search for the best scoring vanity address with the specified Safe
+/// parameters by walking a range of deterministic salt nonces until the limits
+/// are reached or the range is exhausted.
+///
+/// At least one salt nonce is scored before the deadline is checked, so the
+/// shared best is always set once the search returns. The specified callback
+/// is called every time this search finds a Safe that improves on the shared
+/// best so far, and the number of scored salt nonces is added to the attempt
+/// counter.
+pub fn search(
+    safe: &mut Safe,
+    scorer: &(impl Scorer + ?Sized),
+    nonces: &mut Nonces,
+    best: &Best,
+    limits: &Limits,
+    attempts: &Attempts,
+    improved: impl FnMut(u32, &Safe),
+) {
+    let cancellation = CancellationToken::new();
+    search_with(
+        scorer,
+        best,
+        limits,
+        attempts,
+        improved,
+        |matcher, deadline| {
+            let (outcome, stats) =
+                crate::search_until(safe, matcher, nonces, &cancellation, deadline);
+            let found = matches!(outcome, Outcome::Found(_)).then(|| safe.clone());
+            (outcome, found, stats.attempts)
+        },
+    );
+}
+
+/// Search for the best scoring vanity address with the specified Safe
+/// parameters in parallel, using one thread for each of the specified salt
+/// nonce ranges, until the limits are reached or the ranges are exhausted.
+///
+/// This is the parallel version of [`search`], built on top of
+/// [`crate::search_parallel`]. The callback is always called on the current
+/// thread, in order of increasing scores.
+pub fn search_parallel(
+    safe: &Safe,
+    scorer: &(impl Scorer + Sync + ?Sized),
+    ranges: &mut [Nonces],
+    best: &Best,
+    limits: &Limits,
+    attempts: &Attempts,
+    improved: impl FnMut(u32, &Safe),
+) {
+    let cancellation = CancellationToken::new();
+    search_with(
+        scorer,
+        best,
+        limits,
+        attempts,
+        improved,
+        |matcher, deadline| {
+            let result = crate::search_parallel(safe, matcher, ranges, &cancellation, deadline);
+            let count = result.stats.iter().map(|stats| stats.attempts).sum();
+            (result.outcome, result.deployment, count)
+        },
+    );
+}
+
+/// Repeatedly searches for Safes that improve on the best so far with the
+/// specified search function until the limits are reached.
+fn search_with<S>(
+    scorer: &S,
+    best: &Best,
+    limits: &Limits,
+    attempts: &Attempts,
+    mut improved: impl FnMut(u32, &Safe),
+    mut search: impl FnMut(&Improves<S>, Option<Instant>) -> (Outcome, Option<Safe>, u64),
+) where
+    S: Scorer + ?Sized,
+{
+    let matcher = Improves { scorer, best };
+    loop {
+        // Any address improves on no Safe at all, so ignoring the deadline
+        // until the first Safe was found only scores a single salt nonce.
+        let deadline = limits.deadline.filter(|_| best.score().is_some());
+        let (outcome, safe, count) = search(&matcher, deadline);
+        attempts.add(count);
+        if let Some(safe) = safe {
+            let score = scorer.score(&safe.creation_address());
+            if best.offer(score, &safe) {
+                improved(score, &safe);
+            }
+        }
+        if outcome == Outcome::Exhausted || limits.reached(best) {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::safe::tests::safe;
+
+    #[test]
+    fn builtin_scorers() {
+        for (address, leading_zeros, zero_bytes, repeated_nibbles) in [
+            (
+                address!("0000000000000000000000000000000000000000"),
+                40,
+                20,
+                40,
+            ),
+            (
+                address!("000000000000000000000000000000000000000f"),
+                39,
+                19,
+                39,
+            ),
+            (
+                address!("00000fffff000000000000000000000000000000"),
+                5,
+                17,
+                30,
+            ),
+            (
+                address!("0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f"),
+                1,
+                0,
+                1,
+            ),
+            (
+                address!("123455555678abcdef0123456789abcdef012345"),
+                0,
+                0,
+                5,
+            ),
+            (
+                address!("5afe5afe5afe5afe5afe5afe5afe5afe5afe5afe"),
+                0,
+                0,
+                1,
+            ),
+        ] {
+            assert_eq!(LeadingZeros.score(&address), leading_zeros);
+            assert_eq!(ZeroBytes.score(&address), zero_bytes);
+            assert_eq!(RepeatedNibbles.score(&address), repeated_nibbles);
+        }
+    }
+
+    #[test]
+    fn search_finds_best_in_range() {
+        let nonces = Nonces::range(42, 0, 1000);
+        let mut expected = None::<(u32, u128)>;
+        let mut safe = safe();
+        for counter in nonces.start()..nonces.end() {
+            safe.update_salt_nonce(|n| *n = Nonces::salt_nonce(42, counter));
+            let score = ZeroBytes.score(&safe.creation_address());
+            if expected.is_none_or(|(best, _)| score > best) {
+                expected = Some((score, counter));
+            }
+        }
+        let (score, counter) = expected.unwrap();
+
+        let (best, attempts) = (Best::new(), Attempts::new());
+        let mut scores = Vec::new();
+        search(
+            &mut safe,
+            &ZeroBytes,
+            &mut nonces.clone(),
+            &best,
+            &Limits::default(),
+            &attempts,
+            |score, _| scores.push(score),
+        );
+        let (best_score, best_safe) = best.get().unwrap();
+        assert_eq!(best_score, score);
+        assert_eq!(best_safe.salt_nonce(), Nonces::salt_nonce(42, counter));
+        assert!(scores.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(attempts.get(), 1000);
+
+        let (best, attempts) = (Best::new(), Attempts::new());
+        let mut ranges = (0..4).map(|i| nonces.shard(i, 4)).collect::<Vec<_>>();
+        let mut scores = Vec::new();
+        search_parallel(
+            &safe,
+            &ZeroBytes,
+            &mut ranges,
+            &best,
+            &Limits::default(),
+            &attempts,
+            |score, _| scores.push(score),
+        );
+        assert_eq!(best.score(), Some(score));
+        assert!(scores.windows(2).all(|w| w[0] < w[1]));
+        assert!(ranges.iter().all(Nonces::is_empty));
+    }
+
+    #[test]
+    fn search_scores_before_checking_limits() {
+        let (best, attempts) = (Best::new(), Attempts::new());
+        let limits = Limits {
+            min_score: None,
+            deadline: Some(Instant::now()),
+        };
+
+        let mut improvements = 0;
+        search(
+            &mut safe(),
+            &LeadingZeros,
+            &mut Nonces::new(42),
+            &best,
+            &limits,
+            &attempts,
+            |_, _| improvements += 1,
+        );
+        let (score, safe) = best.get().unwrap();
+        assert_eq!(LeadingZeros.score(&safe.creation_address()), score);
+        assert!(improvements > 0);
+        assert!(attempts.get() > 0);
+    }
+}
@@ -0,0 +1,47 @@
+//! Module containing search statistics.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// A counter for the number of attempted salt nonces, cheaply shared across
+/// search threads.
+///
+/// Searches only update the counter once per batch of attempts in order to
+/// avoid contention between threads.
+#[derive(Debug, Default)]
+pub struct Attempts(AtomicU64);
+
+impl Attempts {
+    /// Creates a new attempt counter starting at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the total number of attempts so far.
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Adds a batch of attempts to the counter.
+    pub fn add(&self, count: u64) {
+        self.0.fetch_add(count, Ordering::Relaxed);
+    }
+}
+
+/// Statistics for a completed search.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    /// The number of attempted salt nonces.
+    pub attempts: u64,
+    /// The time spent searching.
+    pub elapsed: Duration,
+}
+
+impl Stats {
+    /// Returns the number of attempts per second.
+    pub fn rate(&self) -> f64 {
+        self.attempts as f64 / self.elapsed.as_secs_f64()
+    }
+}
@@ -15,6 +15,23 @@ export interface Configuration {
   fallbackHandler?: Address;
 }
 
+/**
+ * Additional options for searching for vanity addresses.
+ */
+export interface Options {
+  suffix?: Bytes;
+  /**
+   * A masked address pattern, where `?` is a wildcard for any nibble and `..`
+   * fills the rest of the address with wildcards (e.g. `0xdead..beef`).
+   */
+  pattern?: string;
+  /**
+   * Match the letter case of the pattern against the EIP-55 checksummed
+   * address.
+   */
+  checksum?: boolean;
+}
+
 /**
  * Vanity Safe creation data.
  */
@@ -34,10 +51,10 @@ export interface Transaction {
 
 /**
  * A worker for searching for a vanity Safe address for the specified
- * parameters and prefix.
+ * parameters, prefix and options.
  */
 export declare class DeadbeefWorker {
-  constructor(config: Configuration, prefix: Bytes);
+  constructor(config: Configuration, prefix: Bytes, options?: Options);
   wait(): Promise<Creation>;
   cancel(err?: Error): void;
 }
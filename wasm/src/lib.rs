@@ -1,4 +1,4 @@
-use deadbeef_core::{config, Configuration, Safe};
+use deadbeef_core::{config, Checksum, Configuration, Nibbles, Pattern, Safe};
 use hex::FromHexError;
 use std::error::Error;
 use wasm_bindgen::prelude::*;
@@ -52,6 +52,17 @@ mod js {
         }
     }
 
+    #[derive(Default, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Options {
+        #[serde(default)]
+        pub suffix: Option<String>,
+        #[serde(default)]
+        pub pattern: Option<String>,
+        #[serde(default)]
+        pub checksum: bool,
+    }
+
     #[derive(Serialize)]
     #[serde(rename_all = "camelCase")]
     pub struct Creation {
@@ -69,8 +80,8 @@ mod js {
 }
 
 #[wasm_bindgen]
-pub fn search(safe: JsValue, prefix: &str) -> Result<JsValue, String> {
-    let result = inner(safe, prefix);
+pub fn search(safe: JsValue, prefix: &str, options: JsValue) -> Result<JsValue, String> {
+    let result = inner(safe, prefix, options);
 
     // TODO(nlordell): Ideally, we would just return `Result<JsValue, JsError>`
     // and be done with it. However, it looks like `wasm-pack`/`wasm-bindgen` is
@@ -79,15 +90,34 @@ pub fn search(safe: JsValue, prefix: &str) -> Result<JsValue, String> {
     result.map_err(|err| err.to_string())
 }
 
-fn inner(safe: JsValue, prefix: &str) -> Result<JsValue, Box<dyn Error>> {
+fn inner(safe: JsValue, prefix: &str, options: JsValue) -> Result<JsValue, Box<dyn Error>> {
     let config = serde_wasm_bindgen::from_value::<js::Configuration>(safe)?;
-    let prefix = hex_decode(prefix)?;
+    let options =
+        serde_wasm_bindgen::from_value::<Option<js::Options>>(options)?.unwrap_or_default();
+    let pattern = Pattern::new(
+        prefix.parse()?,
+        options
+            .suffix
+            .as_deref()
+            .map(str::parse::<Nibbles>)
+            .transpose()?
+            .unwrap_or_default(),
+    )?
+    .merge(
+        &options
+            .pattern
+            .as_deref()
+            .map(str::parse::<Pattern>)
+            .transpose()?
+            .unwrap_or_default(),
+    )?;
 
     let mut safe = Safe::new(Configuration {
         proxy: config::Proxy {
             factory: config.proxy_factory.parse()?,
             init_code: hex_decode(&config.proxy_init_code)?,
             singleton: config.singleton.parse()?,
+            chain_specific: None,
         },
         account: config::Account {
             owners: config
@@ -116,7 +146,11 @@ fn inner(safe: JsValue, prefix: &str) -> Result<JsValue, Box<dyn Error>> {
         },
     });
 
-    deadbeef_core::search(&mut safe, &prefix);
+    if options.checksum {
+        deadbeef_core::search(&mut safe, &Checksum::new(pattern));
+    } else {
+        deadbeef_core::search(&mut safe, &pattern);
+    }
 
     let transaction = safe.transaction();
     let creation = serde_wasm_bindgen::to_value(&js::Creation {
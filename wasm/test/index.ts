@@ -53,6 +53,32 @@ Deno.test("computes Safe creation with required configuration", async () => {
   );
 });
 
+Deno.test("computes Safe creation with suffix", async () => {
+  const prefix = "0x5";
+  const suffix = "afe";
+
+  const worker = new DeadbeefWorker(config, prefix, { suffix });
+  const { creationAddress } = await worker.wait();
+
+  assert(
+    creationAddress.toLowerCase().startsWith(prefix) &&
+      creationAddress.toLowerCase().endsWith(suffix),
+    "Safe creation address does not match prefix and suffix",
+  );
+});
+
+Deno.test("computes Safe creation with masked pattern", async () => {
+  const pattern = "0x?0..0";
+
+  const worker = new DeadbeefWorker(config, "0x", { pattern });
+  const { creationAddress } = await worker.wait();
+
+  assert(
+    creationAddress[3] === "0" && creationAddress.endsWith("0"),
+    "Safe creation address does not match pattern",
+  );
+});
+
 Deno.test("cancel resolves to error", async () => {
   const longPrefix = "0x00112233445566778899aabbccddeeff";
   const worker = new DeadbeefWorker(config, longPrefix);